@@ -47,7 +47,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
     let (train_dataset, test_dataset) = train_test_split(dataset, 0.25);
 
-    let model = GaussianNBEstimator
+    let model = GaussianNBEstimator::default()
         .fit(&train_dataset)
         .ok_or("Training failed")?;
     let inference = model
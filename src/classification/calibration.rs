@@ -0,0 +1,500 @@
+//! Probability calibration of fitted classifiers
+
+use ndarray::{stack, Array2, ArrayView1, Axis};
+
+use crate::{seeded_rng, Estimator};
+
+use super::{fold_complement, stratified_folds, ClassificationDataSet, Classifier};
+
+/// Function mapping uncalibrated scores to calibrated probabilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Sigmoid fitted with Platt scaling. Suited to small calibration sets and scores distorted
+    /// symmetrically.
+    Sigmoid,
+    /// Non-decreasing step function fitted with pool adjacent violators. Corrects any monotonic
+    /// distortion, but overfits small calibration sets.
+    Isotonic,
+}
+
+/// Estimator calibrating the probabilities of a classifier with cross-validation.
+///
+/// Records are split into `cv` stratified folds, 5 by default. For each fold, a classifier is
+/// fitted on the other folds, and a calibration function per label is fitted on its probabilities
+/// of the held-out records. The probability of the second of two labels alone is calibrated.
+/// Calibrated probabilities are normalized per record and averaged over the folds.
+///
+/// `fit` returns None if `cv` is less than 2, any label has fewer than `cv` records, or a fold
+/// classifier fails to fit or to predict the labels it was fitted on.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::calibration::{CalibratedClassifierEstimator, Method};
+/// use rs_ml::classification::naive_bayes::GaussianNBEstimator;
+///
+/// // a third of the labels are flipped, and the duplicated feature makes naive Bayes overconfident
+/// let records: Vec<(Array1<f64>, &str)> = (0..40)
+///     .map(|idx| {
+///         let x = (idx % 20) as f64 / 4.;
+///         let label = match (idx % 3 == 1) ^ (x > 2.5) {
+///             true => "high",
+///             false => "low",
+///         };
+///
+///         (arr1(&[x, x + 0.1]), label)
+///     })
+///     .collect();
+///
+/// let dataset = ClassificationDataSet::from(records);
+///
+/// let model = CalibratedClassifierEstimator::new(GaussianNBEstimator::default())
+///     .with_method(Method::Isotonic)
+///     .with_cv(4)
+///     .with_seed(Some(0))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let proba = model.predict_proba([arr1(&[0., 0.1]), arr1(&[5., 5.1])].into_iter()).unwrap();
+///
+/// assert_eq!(model.labels(), &["low", "high"]);
+/// assert!(proba[(0, 1)] < proba[(1, 1)]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CalibratedClassifierEstimator<E> {
+    estimator: E,
+    method: Method,
+    cv: usize,
+    seed: Option<u64>,
+}
+
+/// Represents a classifier with calibrated probabilities. Created with the `fit()` function
+/// implemented for [`CalibratedClassifierEstimator`].
+#[derive(Debug)]
+pub struct CalibratedClassifier<C, Label> {
+    folds: Vec<CalibratedFold<C>>,
+    labels: Vec<Label>,
+}
+
+/// Classifier fitted on all but one fold, with calibration functions fitted on the held-out fold.
+#[derive(Debug)]
+struct CalibratedFold<C> {
+    classifier: C,
+    columns: Vec<usize>,
+    calibrators: Vec<Calibrator>,
+}
+
+#[derive(Debug, Clone)]
+enum Calibrator {
+    Sigmoid(Platt),
+    Isotonic(Isotonic),
+}
+
+/// Non-decreasing function of scores, interpolated linearly between distinct fitted scores.
+#[derive(Debug, Clone)]
+struct Isotonic {
+    thresholds: Vec<f64>,
+    values: Vec<f64>,
+}
+
+/// Sigmoid `1 / (1 + exp(a * f + b))` mapping decision function values `f` to probabilities.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Platt {
+    a: f64,
+    b: f64,
+}
+
+impl Platt {
+    /// Fit a sigmoid to decision function values and their binary outcomes with Newton's method
+    /// and regularized targets, following Lin, Lin and Weng (2007). Returns None if the lengths
+    /// differ or there are no values.
+    pub(crate) fn fit(scores: ArrayView1<f64>, outcomes: &[bool]) -> Option<Platt> {
+        if scores.len() != outcomes.len() || scores.is_empty() {
+            return None;
+        }
+
+        let npositive = outcomes.iter().filter(|o| **o).count() as f64;
+        let nnegative = outcomes.len() as f64 - npositive;
+
+        let high = (npositive + 1.) / (npositive + 2.);
+        let low = 1. / (nnegative + 2.);
+        let targets: Vec<f64> = outcomes
+            .iter()
+            .map(|outcome| match outcome {
+                true => high,
+                false => low,
+            })
+            .collect();
+
+        let objective = |a: f64, b: f64| -> f64 {
+            scores
+                .iter()
+                .zip(&targets)
+                .map(|(f, t)| {
+                    let z = f * a + b;
+
+                    match z >= 0. {
+                        true => t * z + (-z).exp().ln_1p(),
+                        false => (t - 1.) * z + z.exp().ln_1p(),
+                    }
+                })
+                .sum()
+        };
+
+        let mut a = 0.;
+        let mut b = ((nnegative + 1.) / (npositive + 1.)).ln();
+        let mut value = objective(a, b);
+
+        for _ in 0..100 {
+            let (mut h11, mut h22, mut h21, mut g1, mut g2) = (1e-12, 1e-12, 0., 0., 0.);
+
+            for (f, t) in scores.iter().zip(&targets) {
+                let z = f * a + b;
+
+                let (p, q) = match z >= 0. {
+                    true => ((-z).exp() / (1. + (-z).exp()), 1. / (1. + (-z).exp())),
+                    false => (1. / (1. + z.exp()), z.exp() / (1. + z.exp())),
+                };
+
+                let d2 = p * q;
+                h11 += f * f * d2;
+                h22 += d2;
+                h21 += f * d2;
+
+                let d1 = t - p;
+                g1 += f * d1;
+                g2 += d1;
+            }
+
+            if g1.abs() < 1e-5 && g2.abs() < 1e-5 {
+                break;
+            }
+
+            let det = h11 * h22 - h21 * h21;
+            let da = -(h22 * g1 - h21 * g2) / det;
+            let db = -(-h21 * g1 + h11 * g2) / det;
+            let descent = g1 * da + g2 * db;
+
+            let mut step = 1.;
+
+            while step >= 1e-10 {
+                let candidate = objective(a + step * da, b + step * db);
+
+                if candidate < value + 1e-4 * step * descent {
+                    a += step * da;
+                    b += step * db;
+                    value = candidate;
+                    break;
+                }
+
+                step /= 2.;
+            }
+
+            if step < 1e-10 {
+                break;
+            }
+        }
+
+        Some(Platt { a, b })
+    }
+
+    /// Calibrated probability of the positive outcome.
+    pub(crate) fn proba(&self, score: f64) -> f64 {
+        let z = score * self.a + self.b;
+
+        match z >= 0. {
+            true => (-z).exp() / (1. + (-z).exp()),
+            false => 1. / (1. + z.exp()),
+        }
+    }
+
+    /// Probabilities per label from one-vs-rest decision function values. A single column is the
+    /// score of the second of two labels, otherwise calibrated columns are normalized per row.
+    /// Rows without any calibrated probability, or with undefined scores, are uniform.
+    pub(crate) fn one_vs_rest_proba(calibration: &[Platt], scores: &Array2<f64>) -> Array2<f64> {
+        match calibration {
+            [platt] => {
+                let positive = scores.column(0).mapv(|score| platt.proba(score));
+
+                stack![Axis(1), 1. - &positive, positive]
+            }
+            _ => {
+                let mut proba = scores.clone();
+
+                for (mut column, platt) in proba.columns_mut().into_iter().zip(calibration) {
+                    column.mapv_inplace(|score| platt.proba(score));
+                }
+
+                let nlabels = calibration.len();
+
+                for mut row in proba.rows_mut() {
+                    let total = row.sum();
+
+                    match total > 0. && total.is_finite() {
+                        true => row /= total,
+                        false => row.fill(1. / nlabels as f64),
+                    }
+                }
+
+                proba
+            }
+        }
+    }
+}
+
+impl Isotonic {
+    /// Fit a non-decreasing function to scores and their binary outcomes with pool adjacent
+    /// violators. Returns None if the lengths differ or there are no values.
+    fn fit(scores: ArrayView1<f64>, outcomes: &[bool]) -> Option<Isotonic> {
+        if scores.len() != outcomes.len() || scores.is_empty() {
+            return None;
+        }
+
+        let mut order: Vec<usize> = (0..scores.len()).collect();
+        order.sort_by(|a, b| scores[*a].total_cmp(&scores[*b]));
+
+        // (sum of outcomes, number of outcomes, number of distinct scores) per block
+        let mut thresholds: Vec<f64> = vec![];
+        let mut blocks: Vec<(f64, f64, usize)> = vec![];
+
+        for idx in order {
+            let outcome = match outcomes[idx] {
+                true => 1.,
+                false => 0.,
+            };
+
+            match (thresholds.last() == Some(&scores[idx]), blocks.last_mut()) {
+                (true, Some(block)) => {
+                    block.0 += outcome;
+                    block.1 += 1.;
+                }
+                _ => {
+                    thresholds.push(scores[idx]);
+                    blocks.push((outcome, 1., 1));
+                }
+            }
+
+            while let [.., (s1, w1, c1), (s2, w2, c2)] = blocks[..] {
+                if s1 * w2 <= s2 * w1 {
+                    break;
+                }
+
+                blocks.pop();
+                blocks.pop();
+                blocks.push((s1 + s2, w1 + w2, c1 + c2));
+            }
+        }
+
+        let values = blocks
+            .iter()
+            .flat_map(|(sum, weight, count)| std::iter::repeat_n(sum / weight, *count))
+            .collect();
+
+        Some(Isotonic { thresholds, values })
+    }
+
+    /// Fitted value at `score`, clipped to the values at the lowest and highest fitted scores.
+    fn proba(&self, score: f64) -> f64 {
+        let upper = self
+            .thresholds
+            .partition_point(|threshold| *threshold < score);
+
+        match (upper.checked_sub(1), self.thresholds.get(upper)) {
+            (Some(lower), Some(threshold)) => {
+                let fraction =
+                    (score - self.thresholds[lower]) / (threshold - self.thresholds[lower]);
+
+                self.values[lower] + fraction * (self.values[upper] - self.values[lower])
+            }
+            (None, _) => self.values.first().copied().unwrap_or(0.),
+            (_, None) => self.values.last().copied().unwrap_or(0.),
+        }
+    }
+}
+
+impl Calibrator {
+    fn fit(method: Method, scores: ArrayView1<f64>, outcomes: &[bool]) -> Option<Calibrator> {
+        match method {
+            Method::Sigmoid => Platt::fit(scores, outcomes).map(Calibrator::Sigmoid),
+            Method::Isotonic => Isotonic::fit(scores, outcomes).map(Calibrator::Isotonic),
+        }
+    }
+
+    fn proba(&self, score: f64) -> f64 {
+        match self {
+            Calibrator::Sigmoid(platt) => platt.proba(score),
+            Calibrator::Isotonic(isotonic) => isotonic.proba(score),
+        }
+    }
+}
+
+impl<E> CalibratedClassifierEstimator<E> {
+    /// Create a new estimator calibrating classifiers fitted with `estimator`, using sigmoid
+    /// calibration on 5 folds.
+    pub fn new(estimator: E) -> Self {
+        CalibratedClassifierEstimator {
+            estimator,
+            method: Method::Sigmoid,
+            cv: 5,
+            seed: None,
+        }
+    }
+
+    /// Set calibration function.
+    pub fn with_method(self, method: Method) -> Self {
+        CalibratedClassifierEstimator { method, ..self }
+    }
+
+    /// Set number of cross-validation folds, at least 2.
+    pub fn with_cv(self, cv: usize) -> Self {
+        CalibratedClassifierEstimator { cv, ..self }
+    }
+
+    /// Set seed to assign records to folds with, or None to seed from OS entropy.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        CalibratedClassifierEstimator { seed, ..self }
+    }
+}
+
+/// Indices of the labels whose probabilities are calibrated: the second of two labels, otherwise
+/// all labels.
+fn calibrated_labels(nlabels: usize) -> std::ops::Range<usize> {
+    match nlabels {
+        2 => 1..2,
+        _ => 0..nlabels,
+    }
+}
+
+impl<F, L, E> Estimator<ClassificationDataSet<F, L>> for CalibratedClassifierEstimator<E>
+where
+    F: Clone,
+    L: PartialEq + Clone,
+    E: Estimator<ClassificationDataSet<F, L>>,
+    E::Estimator: Classifier<F, L>,
+{
+    type Estimator = CalibratedClassifier<E::Estimator, L>;
+
+    fn fit(&self, input: &ClassificationDataSet<F, L>) -> Option<Self::Estimator> {
+        let labels = input.distinct_labels();
+        let label_indices = input.label_indices(&labels);
+
+        if self.cv < 2 || label_indices.iter().any(|indeces| indeces.len() < self.cv) {
+            return None;
+        }
+
+        let folds = stratified_folds(&label_indices, self.cv, &mut seeded_rng(self.seed));
+
+        let folds = folds
+            .iter()
+            .enumerate()
+            .map(|(fold, held_out)| {
+                let train = fold_complement(&folds, fold);
+
+                let classifier = self.estimator.fit(&input.select(&train, L::clone))?;
+
+                let columns = labels
+                    .iter()
+                    .map(|label| classifier.labels().iter().position(|l| l == label))
+                    .collect::<Option<Vec<usize>>>()?;
+
+                let proba = classifier.predict_proba(
+                    held_out
+                        .iter()
+                        .map(|idx| input.dataset[*idx].features.clone()),
+                )?;
+
+                let calibrators = calibrated_labels(labels.len())
+                    .map(|label| {
+                        let outcomes: Vec<bool> = held_out
+                            .iter()
+                            .map(|idx| input.dataset[*idx].label == labels[label])
+                            .collect();
+
+                        Calibrator::fit(self.method, proba.column(columns[label]), &outcomes)
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                Some(CalibratedFold {
+                    classifier,
+                    columns,
+                    calibrators,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(CalibratedClassifier { folds, labels })
+    }
+}
+
+impl<C, Label> CalibratedClassifier<C, Label> {
+    /// Classifiers fitted on each combination of all but one fold.
+    pub fn classifiers(&self) -> impl Iterator<Item = &C> {
+        self.folds.iter().map(|fold| &fold.classifier)
+    }
+}
+
+impl<C> CalibratedFold<C> {
+    /// Calibrated probabilities per label, normalized per record.
+    fn proba<F, L>(&self, features: &[F], nlabels: usize) -> Option<Array2<f64>>
+    where
+        F: Clone,
+        L: Clone,
+        C: Classifier<F, L>,
+    {
+        let proba = self.classifier.predict_proba(features.iter().cloned())?;
+
+        let mut calibrated = Array2::ones((features.len(), nlabels));
+
+        for (label, calibrator) in calibrated_labels(nlabels).zip(&self.calibrators) {
+            let column = proba.column(self.columns[label]);
+
+            calibrated
+                .column_mut(label)
+                .assign(&column.mapv(|score| calibrator.proba(score)));
+        }
+
+        if nlabels == 2 {
+            let positive = calibrated.column(1).to_owned();
+
+            return Some(stack![Axis(1), 1. - &positive, positive]);
+        }
+
+        for mut row in calibrated.rows_mut() {
+            let total = row.sum();
+
+            match total > 0. {
+                true => row /= total,
+                false => row.fill(1. / nlabels as f64),
+            }
+        }
+
+        Some(calibrated)
+    }
+}
+
+impl<F, L, C> Classifier<F, L> for CalibratedClassifier<C, L>
+where
+    F: Clone,
+    L: Clone,
+    C: Classifier<F, L>,
+{
+    fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = F>,
+    {
+        let features: Vec<F> = arr.collect();
+        let mut proba = Array2::zeros((features.len(), self.labels.len()));
+
+        for fold in &self.folds {
+            proba += &fold.proba::<F, L>(&features, self.labels.len())?;
+        }
+
+        Some(proba / self.folds.len() as f64)
+    }
+}
@@ -0,0 +1,365 @@
+//! Discriminant analysis classifiers
+
+use std::marker::PhantomData;
+
+use ndarray::{Array1, Array2, Array3, Axis};
+use ndarray_linalg::{Eigh, Inverse, UPLO};
+
+use crate::{Estimatable, Estimator};
+
+use super::{softmax, to_feature_matrix, ClassificationDataSet, Classifier};
+
+/// Estimator to train a [`LinearDiscriminantAnalysis`] classifier. Each label is modeled as a
+/// multivariate normal distribution with its own mean and a covariance matrix shared by all
+/// labels, which gives linear decision boundaries.
+///
+/// The shared covariance is the pooled maximum likelihood estimate of the covariance within each
+/// label. With shrinkage `s`, it is replaced by `(1 - s) * covariance + s * mean_variance * I`,
+/// which keeps it invertible with few records or many correlated features. Priors are the label
+/// frequencies. Fitting fails if the shrinkage is not between 0 and 1, or if the shared
+/// covariance is singular.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::discriminant_analysis::LinearDiscriminantAnalysisEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[1., 2.]), "a"),
+///     (arr1(&[2., 2.5]), "a"),
+///     (arr1(&[2., 4.]), "a"),
+///     (arr1(&[5., 5.]), "b"),
+///     (arr1(&[6., 5.5]), "b"),
+///     (arr1(&[6., 7.]), "b"),
+/// ]);
+///
+/// let model = LinearDiscriminantAnalysisEstimator::default()
+///     .with_shrinkage(Some(0.1))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[1.5, 3.]), arr1(&[6., 6.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["a", "b"]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinearDiscriminantAnalysisEstimator {
+    shrinkage: Option<f64>,
+}
+
+/// Represents a fitted linear discriminant analysis classifier. Created with the `fit()` function
+/// implemented for [`LinearDiscriminantAnalysisEstimator`].
+#[derive(Debug)]
+pub struct LinearDiscriminantAnalysis<Input, Label> {
+    _input: PhantomData<Input>,
+    means: Array2<f64>,
+    covariance: Array2<f64>,
+    coefficients: Array2<f64>,
+    intercepts: Array1<f64>,
+    labels: Vec<Label>,
+}
+
+/// Estimator to train a [`QuadraticDiscriminantAnalysis`] classifier. Each label is modeled as a
+/// multivariate normal distribution with its own mean and covariance matrix, which gives
+/// quadratic decision boundaries.
+///
+/// The covariance of each label is the unbiased estimate of the records with that label. With
+/// regularization `r`, it is replaced by `(1 - r) * covariance + r * I`. Priors are the label
+/// frequencies. Fitting fails if the regularization is not between 0 and 1, or if the covariance
+/// of any label is singular, which is always the case for labels with fewer records than
+/// features unless regularized.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::discriminant_analysis::QuadraticDiscriminantAnalysisEstimator;
+///
+/// // both labels are centered on the origin, but differ in spread
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[0.1, 0.]), "narrow"),
+///     (arr1(&[-0.1, 0.1]), "narrow"),
+///     (arr1(&[0., -0.1]), "narrow"),
+///     (arr1(&[3., 0.]), "wide"),
+///     (arr1(&[-3., 3.]), "wide"),
+///     (arr1(&[0., -3.]), "wide"),
+/// ]);
+///
+/// let model = QuadraticDiscriminantAnalysisEstimator::default()
+///     .with_reg_param(0.01)
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[0., 0.]), arr1(&[2., 2.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["narrow", "wide"]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuadraticDiscriminantAnalysisEstimator {
+    reg_param: f64,
+}
+
+/// Represents a fitted quadratic discriminant analysis classifier. Created with the `fit()`
+/// function implemented for [`QuadraticDiscriminantAnalysisEstimator`].
+#[derive(Debug)]
+pub struct QuadraticDiscriminantAnalysis<Input, Label> {
+    _input: PhantomData<Input>,
+    means: Array2<f64>,
+    rotations: Array3<f64>,
+    scalings: Array2<f64>,
+    log_priors: Array1<f64>,
+    labels: Vec<Label>,
+}
+
+impl LinearDiscriminantAnalysisEstimator {
+    /// Set shrinkage of the shared covariance towards a scaled identity matrix, between 0 and 1,
+    /// or None to use the empirical covariance.
+    pub fn with_shrinkage(self, shrinkage: Option<f64>) -> Self {
+        LinearDiscriminantAnalysisEstimator { shrinkage }
+    }
+}
+
+impl QuadraticDiscriminantAnalysisEstimator {
+    /// Set regularization of the covariance of each label towards the identity matrix, between
+    /// 0 and 1.
+    pub fn with_reg_param(self, reg_param: f64) -> Self {
+        QuadraticDiscriminantAnalysisEstimator { reg_param }
+    }
+}
+
+/// Mean and centered records of a label.
+fn center(features: &Array2<f64>, indeces: &[usize]) -> Option<(Array1<f64>, Array2<f64>)> {
+    let records = features.select(Axis(0), indeces);
+    let mean = records.mean_axis(Axis(0))?;
+    let centered = records - &mean;
+
+    Some((mean, centered))
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for LinearDiscriminantAnalysisEstimator
+{
+    type Estimator = LinearDiscriminantAnalysis<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if self
+            .shrinkage
+            .is_some_and(|shrinkage| !(0. ..=1.).contains(&shrinkage))
+        {
+            return None;
+        }
+
+        let features = input.features_array()?;
+        let distinct_labels = input.distinct_labels();
+        let indices = input.label_indices(&distinct_labels);
+        let (nrecords, nfeatures) = features.dim();
+
+        let mut means = Array2::zeros((distinct_labels.len(), nfeatures));
+        let mut scatter = Array2::zeros((nfeatures, nfeatures));
+
+        for (idx, indeces) in indices.iter().enumerate() {
+            let (mean, centered) = center(&features, indeces)?;
+
+            means.row_mut(idx).assign(&mean);
+            scatter += &centered.t().dot(&centered);
+        }
+
+        let mut covariance = scatter / nrecords as f64;
+
+        if let Some(shrinkage) = self.shrinkage {
+            let mean_variance = covariance.diag().mean()?;
+
+            covariance *= 1. - shrinkage;
+            covariance
+                .diag_mut()
+                .mapv_inplace(|v| v + shrinkage * mean_variance);
+        }
+
+        let precision = covariance.inv().ok()?;
+        let coefficients = means.dot(&precision);
+
+        let intercepts: Array1<f64> = indices
+            .iter()
+            .zip(coefficients.rows())
+            .zip(means.rows())
+            .map(|((indeces, coefficient), mean)| {
+                -0.5 * coefficient.dot(&mean) + (indeces.len() as f64 / nrecords as f64).ln()
+            })
+            .collect();
+
+        if !coefficients.iter().all(|c| c.is_finite()) {
+            return None;
+        }
+
+        Some(LinearDiscriminantAnalysis {
+            _input: PhantomData,
+            means,
+            covariance,
+            coefficients,
+            intercepts,
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<Input, Label> LinearDiscriminantAnalysis<Input, Label> {
+    /// Mean of each label, with one row per label in the same order as the labels of the model.
+    pub fn means(&self) -> &Array2<f64> {
+        &self.means
+    }
+
+    /// Covariance matrix shared by all labels, after shrinkage.
+    pub fn covariance(&self) -> &Array2<f64> {
+        &self.covariance
+    }
+
+    /// Coefficients of the linear discriminant function of each label, with one row per label.
+    pub fn coefficients(&self) -> &Array2<f64> {
+        &self.coefficients
+    }
+
+    /// Intercepts of the linear discriminant function of each label.
+    pub fn intercepts(&self) -> &Array1<f64> {
+        &self.intercepts
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label>
+    for LinearDiscriminantAnalysis<Input, Label>
+{
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.coefficients.ncols(),
+        )?;
+
+        Some(softmax(
+            features.dot(&self.coefficients.t()) + &self.intercepts,
+        ))
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for QuadraticDiscriminantAnalysisEstimator
+{
+    type Estimator = QuadraticDiscriminantAnalysis<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if !(0. ..=1.).contains(&self.reg_param) {
+            return None;
+        }
+
+        let features = input.features_array()?;
+        let distinct_labels = input.distinct_labels();
+        let indices = input.label_indices(&distinct_labels);
+        let (nrecords, nfeatures) = features.dim();
+        let nclasses = distinct_labels.len();
+
+        let mut means = Array2::zeros((nclasses, nfeatures));
+        let mut rotations = Array3::zeros((nclasses, nfeatures, nfeatures));
+        let mut scalings = Array2::zeros((nclasses, nfeatures));
+
+        for (idx, indeces) in indices.iter().enumerate() {
+            let (mean, centered) = center(&features, indeces)?;
+
+            let mut covariance = match indeces.len() {
+                1 => Array2::zeros((nfeatures, nfeatures)),
+                count => centered.t().dot(&centered) / (count - 1) as f64,
+            };
+
+            covariance *= 1. - self.reg_param;
+            covariance.diag_mut().mapv_inplace(|v| v + self.reg_param);
+
+            let (eigen_values, eigen_vectors) = covariance.eigh(UPLO::Upper).ok()?;
+
+            if eigen_values.iter().any(|v| *v <= 1e-12 || !v.is_finite()) {
+                return None;
+            }
+
+            means.row_mut(idx).assign(&mean);
+            rotations
+                .index_axis_mut(Axis(0), idx)
+                .assign(&eigen_vectors);
+            scalings.row_mut(idx).assign(&eigen_values);
+        }
+
+        let log_priors = indices
+            .iter()
+            .map(|indeces| (indeces.len() as f64 / nrecords as f64).ln())
+            .collect();
+
+        Some(QuadraticDiscriminantAnalysis {
+            _input: PhantomData,
+            means,
+            rotations,
+            scalings,
+            log_priors,
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<Input, Label> QuadraticDiscriminantAnalysis<Input, Label> {
+    /// Mean of each label, with one row per label in the same order as the labels of the model.
+    pub fn means(&self) -> &Array2<f64> {
+        &self.means
+    }
+
+    /// Covariance matrix of each label after regularization, indexed by label along the first
+    /// axis.
+    pub fn covariances(&self) -> Array3<f64> {
+        let mut covariances = Array3::zeros(self.rotations.dim());
+
+        for (idx, mut covariance) in covariances.outer_iter_mut().enumerate() {
+            let rotation = self.rotations.index_axis(Axis(0), idx);
+            let scaled = &rotation * &self.scalings.row(idx);
+
+            covariance.assign(&scaled.dot(&rotation.t()));
+        }
+
+        covariances
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label>
+    for QuadraticDiscriminantAnalysis<Input, Label>
+{
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.means.ncols(),
+        )?;
+
+        let mut jll = Array2::zeros((features.nrows(), self.labels.len()));
+
+        for (idx, mut column) in jll.columns_mut().into_iter().enumerate() {
+            let rotation = self.rotations.index_axis(Axis(0), idx);
+            let scaling = self.scalings.row(idx);
+
+            let projected = (&features - &self.means.row(idx)).dot(&rotation) / scaling.sqrt();
+            let distances = projected.pow2().sum_axis(Axis(1));
+            let log_det = scaling.ln().sum();
+
+            column.assign(&(-0.5 * (distances + log_det) + self.log_priors[idx]));
+        }
+
+        Some(softmax(jll))
+    }
+}
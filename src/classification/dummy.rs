@@ -0,0 +1,221 @@
+//! Baseline classifiers ignoring the features of records
+
+use std::marker::PhantomData;
+
+use ndarray::{Array1, Array2};
+use rand::Rng;
+
+use crate::{argmax, seeded_rng, Estimator};
+
+use super::{ClassificationDataSet, Classifier};
+
+/// Rule by which a [`DummyClassifier`] predicts labels.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Strategy<Label> {
+    /// Always predict the most frequent label, the first to appear among equally frequent labels.
+    MostFrequent,
+    /// Predict labels drawn at random with their frequencies in the dataset.
+    Stratified,
+    /// Predict labels drawn uniformly at random. Probabilities are equal for all labels.
+    Uniform,
+    /// Always predict the given label, which has to be in the dataset.
+    Constant(Label),
+}
+
+/// Estimator to train a [`DummyClassifier`], a baseline which ignores features and only uses the
+/// labels of the dataset.
+///
+/// Random strategies draw labels from a generator seeded anew by each call to `predict_proba()` or
+/// `predict()`, so predictions are reproducible when a seed is set. Random draws are one-hot
+/// probabilities for [`Strategy::Stratified`].
+///
+/// `fit` returns None if the dataset is empty, or the label of [`Strategy::Constant`] is not in
+/// the dataset.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::dummy::{DummyClassifierEstimator, Strategy};
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[0.]), "ham"),
+///     (arr1(&[1.]), "spam"),
+///     (arr1(&[2.]), "ham"),
+/// ]);
+///
+/// let model = DummyClassifierEstimator::default().fit(&dataset).unwrap();
+///
+/// assert_eq!(model.priors(), &arr1(&[2. / 3., 1. / 3.]));
+/// assert_eq!(model.predict([arr1(&[1.])].into_iter()).unwrap(), vec!["ham"]);
+///
+/// let model = DummyClassifierEstimator::new(Strategy::Constant("spam"))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// assert_eq!(model.predict([arr1(&[0.])].into_iter()).unwrap(), vec!["spam"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DummyClassifierEstimator<Label> {
+    strategy: Strategy<Label>,
+    seed: Option<u64>,
+}
+
+/// Represents a fitted baseline classifier. Created with the `fit()` function implemented for
+/// [`DummyClassifierEstimator`].
+#[derive(Debug, Clone)]
+pub struct DummyClassifier<Input, Label> {
+    _input: PhantomData<Input>,
+    strategy: Strategy<Label>,
+    priors: Array1<f64>,
+    labels: Vec<Label>,
+    seed: Option<u64>,
+}
+
+impl<Label> Default for DummyClassifierEstimator<Label> {
+    fn default() -> Self {
+        DummyClassifierEstimator::new(Strategy::MostFrequent)
+    }
+}
+
+impl<Label> DummyClassifierEstimator<Label> {
+    /// Create a new estimator predicting labels with `strategy`.
+    pub fn new(strategy: Strategy<Label>) -> Self {
+        DummyClassifierEstimator {
+            strategy,
+            seed: None,
+        }
+    }
+
+    /// Set prediction rule.
+    pub fn with_strategy(self, strategy: Strategy<Label>) -> Self {
+        DummyClassifierEstimator { strategy, ..self }
+    }
+
+    /// Set seed to draw random labels with, or None to seed from OS entropy.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        DummyClassifierEstimator { seed, ..self }
+    }
+}
+
+impl<Input, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for DummyClassifierEstimator<Label>
+{
+    type Estimator = DummyClassifier<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        let labels = input.distinct_labels();
+        let nrecords = input.get_records().len();
+
+        if nrecords == 0 {
+            return None;
+        }
+
+        if let Strategy::Constant(constant) = &self.strategy {
+            labels.iter().position(|label| label == constant)?;
+        }
+
+        let priors = input
+            .label_indices(&labels)
+            .iter()
+            .map(|indeces| indeces.len() as f64 / nrecords as f64)
+            .collect();
+
+        Some(DummyClassifier {
+            _input: PhantomData,
+            strategy: self.strategy.clone(),
+            priors,
+            labels,
+            seed: self.seed,
+        })
+    }
+}
+
+impl<Input, Label> DummyClassifier<Input, Label> {
+    /// Frequency of each label in the dataset.
+    pub fn priors(&self) -> &Array1<f64> {
+        &self.priors
+    }
+}
+
+impl<Input, Label: PartialEq> DummyClassifier<Input, Label> {
+    /// Index of the label always predicted, or None if labels are drawn at random.
+    fn fixed_label(&self) -> Option<usize> {
+        match &self.strategy {
+            Strategy::MostFrequent => argmax(self.priors.iter().copied()),
+            Strategy::Constant(constant) => self.labels.iter().position(|label| label == constant),
+            Strategy::Stratified | Strategy::Uniform => None,
+        }
+    }
+
+    /// Draw a label index per record, weighted by `weights`.
+    fn draw(&self, nrecords: usize, weights: &Array1<f64>) -> Vec<usize> {
+        let mut rng = seeded_rng(self.seed);
+        let total = weights.sum();
+
+        (0..nrecords)
+            .map(|_| {
+                let mut remainder = rng.random_range(0.0..total);
+
+                weights
+                    .iter()
+                    .position(|weight| {
+                        remainder -= weight;
+                        remainder < 0.
+                    })
+                    .unwrap_or(weights.len() - 1)
+            })
+            .collect()
+    }
+}
+
+impl<Input, Label: PartialEq + Clone> Classifier<Input, Label> for DummyClassifier<Input, Label> {
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let nrecords = arr.count();
+        let nlabels = self.labels.len();
+
+        let rows = match (&self.strategy, self.fixed_label()) {
+            (Strategy::Uniform, _) => vec![None; nrecords],
+            (_, Some(idx)) => vec![Some(idx); nrecords],
+            (_, None) => self
+                .draw(nrecords, &self.priors)
+                .into_iter()
+                .map(Some)
+                .collect(),
+        };
+
+        let proba = Array2::from_shape_fn((nrecords, nlabels), |(row, column)| match rows[row] {
+            Some(idx) if idx == column => 1.,
+            Some(_) => 0.,
+            None => 1. / nlabels as f64,
+        });
+
+        Some(proba)
+    }
+
+    fn predict<I>(&self, arr: I) -> Option<Vec<Label>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let nrecords = arr.count();
+
+        let indeces = match (&self.strategy, self.fixed_label()) {
+            (Strategy::Uniform, _) => self.draw(nrecords, &Array1::ones(self.labels.len())),
+            (Strategy::Stratified, _) => self.draw(nrecords, &self.priors),
+            (_, idx) => vec![idx?; nrecords],
+        };
+
+        indeces
+            .into_iter()
+            .map(|idx| self.labels.get(idx).cloned())
+            .collect()
+    }
+}
@@ -0,0 +1,523 @@
+//! Logistic regression classifiers
+
+use std::{collections::VecDeque, marker::PhantomData};
+
+use ndarray::{s, Array1, Array2, Axis};
+use rand::seq::SliceRandom;
+
+use crate::{seeded_rng, Estimatable, Estimator};
+
+use super::{log_softmax, softmax, to_feature_matrix, ClassificationDataSet, Classifier};
+
+/// Regularization penalty on the coefficients of a logistic regression. Intercepts are never
+/// penalized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Penalty {
+    /// No regularization.
+    None,
+    /// Lasso penalty `alpha * |w|`, which drives coefficients of uninformative features to zero.
+    L1(f64),
+    /// Ridge penalty `alpha / 2 * |w|^2`.
+    L2(f64),
+    /// Mix of L1 and L2 penalty:
+    /// `alpha * (l1_ratio * |w| + (1 - l1_ratio) / 2 * |w|^2)`.
+    ElasticNet {
+        /// Overall strength of the penalty.
+        alpha: f64,
+        /// Portion of the penalty applied as L1 penalty, between 0 and 1.
+        l1_ratio: f64,
+    },
+}
+
+/// Optimization algorithm used to fit a logistic regression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Solver {
+    /// Limited memory BFGS over the full dataset, keeping the last `memory` updates to
+    /// approximate the Hessian. Only supports [`Penalty::None`] and [`Penalty::L2`].
+    Lbfgs {
+        /// Number of past updates to keep.
+        memory: usize,
+    },
+    /// Mini batch stochastic gradient descent. L1 penalties are applied with a proximal step, so
+    /// all penalties are supported. The step size decays with the square root of the epoch.
+    Sgd {
+        /// Initial step size.
+        learning_rate: f64,
+        /// Number of records per gradient step.
+        batch_size: usize,
+        /// Seed to shuffle records with, or None to seed from OS entropy.
+        seed: Option<u64>,
+    },
+}
+
+/// Estimator to train a [`LogisticRegression`] classifier. Two labels are modeled with a single
+/// set of coefficients for the second label, more labels are modeled with a multinomial (softmax)
+/// model with one set of coefficients per label.
+///
+/// By default, the model is fitted with [`Solver::Lbfgs`] and a [`Penalty::L2`] of `1e-4`, for at
+/// most 100 iterations or until the largest gradient is below a tolerance of `1e-4`. Fitting
+/// fails if there are fewer than two labels, the hyperparameters are invalid, or the solver does
+/// not support the penalty.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::logistic::{LogisticRegressionEstimator, Penalty};
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 0.]), false),
+///     (arr1(&[1., 0.]), false),
+///     (arr1(&[0., 1.]), false),
+///     (arr1(&[3., 3.]), true),
+///     (arr1(&[4., 3.]), true),
+///     (arr1(&[3., 4.]), true),
+/// ]);
+///
+/// let model = LogisticRegressionEstimator::default()
+///     .with_penalty(Penalty::L2(0.01))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[0.5, 0.5]), arr1(&[3.5, 3.5])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec![false, true]);
+/// assert_eq!(model.coefficients().dim(), (1, 2));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LogisticRegressionEstimator {
+    penalty: Penalty,
+    solver: Solver,
+    tol: f64,
+    max_iter: usize,
+    fit_intercept: bool,
+}
+
+impl Default for LogisticRegressionEstimator {
+    fn default() -> Self {
+        LogisticRegressionEstimator {
+            penalty: Penalty::L2(1e-4),
+            solver: Solver::Lbfgs { memory: 10 },
+            tol: 1e-4,
+            max_iter: 100,
+            fit_intercept: true,
+        }
+    }
+}
+
+impl LogisticRegressionEstimator {
+    /// Set regularization penalty.
+    pub fn with_penalty(self, penalty: Penalty) -> Self {
+        LogisticRegressionEstimator { penalty, ..self }
+    }
+
+    /// Set optimization algorithm.
+    pub fn with_solver(self, solver: Solver) -> Self {
+        LogisticRegressionEstimator { solver, ..self }
+    }
+
+    /// Set tolerance for convergence. [`Solver::Lbfgs`] stops once the largest gradient is below
+    /// the tolerance, [`Solver::Sgd`] once an epoch improves the loss by less than the tolerance.
+    pub fn with_tol(self, tol: f64) -> Self {
+        LogisticRegressionEstimator { tol, ..self }
+    }
+
+    /// Set maximum number of iterations, or epochs for [`Solver::Sgd`].
+    pub fn with_max_iter(self, max_iter: usize) -> Self {
+        LogisticRegressionEstimator { max_iter, ..self }
+    }
+
+    /// Set whether to fit an intercept per label.
+    pub fn with_fit_intercept(self, fit_intercept: bool) -> Self {
+        LogisticRegressionEstimator {
+            fit_intercept,
+            ..self
+        }
+    }
+
+    /// L1 and L2 strength of the penalty, or None if the penalty is invalid.
+    fn penalty_strengths(&self) -> Option<(f64, f64)> {
+        let (l1, l2) = match self.penalty {
+            Penalty::None => (0., 0.),
+            Penalty::L1(alpha) => (alpha, 0.),
+            Penalty::L2(alpha) => (0., alpha),
+            Penalty::ElasticNet { alpha, l1_ratio } => {
+                if !(0. ..=1.).contains(&l1_ratio) {
+                    return None;
+                }
+
+                (alpha * l1_ratio, alpha * (1. - l1_ratio))
+            }
+        };
+
+        match l1 >= 0. && l2 >= 0. {
+            true => Some((l1, l2)),
+            false => None,
+        }
+    }
+}
+
+/// Represents a fitted logistic regression classifier. Created with the `fit()` function
+/// implemented for [`LogisticRegressionEstimator`].
+#[derive(Debug)]
+pub struct LogisticRegression<Input, Label> {
+    _input: PhantomData<Input>,
+    coefficients: Array2<f64>,
+    intercepts: Array1<f64>,
+    labels: Vec<Label>,
+}
+
+impl<Input, Label> LogisticRegression<Input, Label> {
+    /// Coefficients of the model with one column per feature. For two labels, there is a single
+    /// row for the second label. Otherwise there is one row per label, in the same order as the
+    /// labels of the model.
+    pub fn coefficients(&self) -> &Array2<f64> {
+        &self.coefficients
+    }
+
+    /// Intercepts of the model, one per row of [`LogisticRegression::coefficients`].
+    pub fn intercepts(&self) -> &Array1<f64> {
+        &self.intercepts
+    }
+}
+
+/// Log loss of a (multinomial) logistic regression with elastic net penalty. Parameters are
+/// stored as a matrix with a row per output and a column per feature, followed by the intercept
+/// column.
+struct LogLoss<'a> {
+    features: &'a Array2<f64>,
+    targets: &'a Array2<f64>,
+    l1: f64,
+    l2: f64,
+    fit_intercept: bool,
+}
+
+impl LogLoss<'_> {
+    fn scores(&self, features: &Array2<f64>, params: &Array2<f64>) -> Array2<f64> {
+        let nfeatures = features.ncols();
+
+        features.dot(&params.slice(s![.., ..nfeatures]).t()) + params.column(nfeatures)
+    }
+
+    /// Loss and gradient over the given records, excluding the L1 penalty.
+    fn loss_grad(&self, params: &Array2<f64>, indeces: Option<&[usize]>) -> (f64, Array2<f64>) {
+        let (features, targets) = match indeces {
+            Some(indeces) => (
+                self.features.select(Axis(0), indeces),
+                self.targets.select(Axis(0), indeces),
+            ),
+            None => (self.features.to_owned(), self.targets.to_owned()),
+        };
+
+        let nrows = features.nrows() as f64;
+        let nfeatures = features.ncols();
+        let scores = self.scores(&features, params);
+
+        let (loss, probs) = match scores.ncols() {
+            1 => {
+                let loss = scores
+                    .iter()
+                    .zip(targets.iter())
+                    .map(|(s, y)| s.max(0.) + (-s.abs()).exp().ln_1p() - y * s)
+                    .sum::<f64>();
+
+                (loss, scores.mapv(sigmoid))
+            }
+            _ => {
+                let log_probs = log_softmax(scores);
+                let loss = -(&log_probs * &targets).sum();
+
+                (loss, log_probs.exp())
+            }
+        };
+
+        let residuals = probs - &targets;
+        let mut grad = Array2::zeros(params.dim());
+
+        grad.slice_mut(s![.., ..nfeatures])
+            .assign(&(residuals.t().dot(&features) / nrows));
+
+        if self.fit_intercept {
+            grad.column_mut(nfeatures)
+                .assign(&(residuals.sum_axis(Axis(0)) / nrows));
+        }
+
+        let coefficients = params.slice(s![.., ..nfeatures]);
+        grad.slice_mut(s![.., ..nfeatures])
+            .scaled_add(self.l2, &coefficients);
+
+        let penalty = 0.5 * self.l2 * coefficients.pow2().sum();
+
+        (loss / nrows + penalty, grad)
+    }
+
+    /// Loss over all records, excluding the L1 penalty.
+    fn smooth_loss(&self, params: &Array2<f64>) -> f64 {
+        self.loss_grad(params, None).0
+    }
+
+    /// Loss over all records, including the L1 penalty.
+    fn loss(&self, params: &Array2<f64>) -> f64 {
+        let nfeatures = self.features.ncols();
+
+        self.smooth_loss(params) + self.l1 * params.slice(s![.., ..nfeatures]).abs().sum()
+    }
+}
+
+fn sigmoid(v: f64) -> f64 {
+    1. / (1. + (-v).exp())
+}
+
+/// Minimize log loss with limited memory BFGS and a backtracking line search.
+fn lbfgs(
+    problem: &LogLoss,
+    mut params: Array2<f64>,
+    memory: usize,
+    tol: f64,
+    max_iter: usize,
+) -> Array2<f64> {
+    let mut history: VecDeque<(Array2<f64>, Array2<f64>, f64)> = VecDeque::with_capacity(memory);
+    let (mut loss, mut grad) = problem.loss_grad(&params, None);
+
+    for _ in 0..max_iter {
+        if grad.fold(0., |agg: f64, curr| agg.max(curr.abs())) < tol {
+            break;
+        }
+
+        // two loop recursion to approximate the inverse Hessian times the gradient.
+        let mut direction = grad.clone();
+        let mut alphas = Vec::with_capacity(history.len());
+
+        for (step, grad_change, rho) in history.iter().rev() {
+            let alpha = rho * (step * &direction).sum();
+            direction.scaled_add(-alpha, grad_change);
+            alphas.push(alpha);
+        }
+
+        let gamma = match history.back() {
+            Some((step, grad_change, _)) => (step * grad_change).sum() / grad_change.pow2().sum(),
+            None => 1. / grad.pow2().sum().sqrt().max(1.),
+        };
+        direction *= gamma;
+
+        for ((step, grad_change, rho), alpha) in history.iter().zip(alphas.into_iter().rev()) {
+            let beta = rho * (grad_change * &direction).sum();
+            direction.scaled_add(alpha - beta, step);
+        }
+
+        direction *= -1.;
+
+        let slope = (&grad * &direction).sum();
+        if slope >= 0. {
+            break;
+        }
+
+        let mut step_size = 1.;
+        let mut next = None;
+
+        for _ in 0..50 {
+            let candidate = &params + &(&direction * step_size);
+            let candidate_loss = problem.smooth_loss(&candidate);
+
+            if candidate_loss <= loss + 1e-4 * step_size * slope {
+                next = Some((candidate, candidate_loss));
+                break;
+            }
+
+            step_size *= 0.5;
+        }
+
+        let Some((next_params, _)) = next else {
+            break;
+        };
+
+        let (next_loss, next_grad) = problem.loss_grad(&next_params, None);
+
+        let step = &next_params - &params;
+        let grad_change = &next_grad - &grad;
+        let curvature = (&step * &grad_change).sum();
+
+        if curvature > 1e-10 {
+            if history.len() == memory {
+                history.pop_front();
+            }
+
+            history.push_back((step, grad_change, 1. / curvature));
+        }
+
+        params = next_params;
+        loss = next_loss;
+        grad = next_grad;
+    }
+
+    params
+}
+
+/// Minimize log loss with mini batch stochastic gradient descent, applying the L1 penalty with a
+/// soft thresholding step.
+fn sgd(
+    problem: &LogLoss,
+    mut params: Array2<f64>,
+    learning_rate: f64,
+    batch_size: usize,
+    seed: Option<u64>,
+    tol: f64,
+    max_iter: usize,
+) -> Array2<f64> {
+    let mut rng = seeded_rng(seed);
+    let nfeatures = problem.features.ncols();
+    let mut indeces: Vec<usize> = (0..problem.features.nrows()).collect();
+
+    let mut loss = problem.loss(&params);
+
+    for epoch in 0..max_iter {
+        let step_size = learning_rate / ((epoch + 1) as f64).sqrt();
+        indeces.shuffle(&mut rng);
+
+        for batch in indeces.chunks(batch_size) {
+            let (_, grad) = problem.loss_grad(&params, Some(batch));
+            params.scaled_add(-step_size, &grad);
+
+            let threshold = step_size * problem.l1;
+            params
+                .slice_mut(s![.., ..nfeatures])
+                .mapv_inplace(|w| w.signum() * (w.abs() - threshold).max(0.));
+        }
+
+        let next_loss = problem.loss(&params);
+        let improvement = loss - next_loss;
+        loss = next_loss;
+
+        if improvement.abs() < tol {
+            break;
+        }
+    }
+
+    params
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for LogisticRegressionEstimator
+{
+    type Estimator = LogisticRegression<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        let (l1, l2) = self.penalty_strengths()?;
+
+        if self.tol <= 0. {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+
+        if distinct_labels.len() < 2 {
+            return None;
+        }
+
+        let features = input.features_array()?;
+        let nfeatures = features.ncols();
+
+        let noutputs = match distinct_labels.len() {
+            2 => 1,
+            n => n,
+        };
+
+        let mut targets = Array2::zeros((features.nrows(), noutputs));
+
+        for (idx, indeces) in input.label_indices(&distinct_labels).iter().enumerate() {
+            let output = match noutputs {
+                1 if idx == 0 => continue,
+                1 => 0,
+                _ => idx,
+            };
+
+            for i in indeces {
+                targets[(*i, output)] = 1.;
+            }
+        }
+
+        let problem = LogLoss {
+            features: &features,
+            targets: &targets,
+            l1,
+            l2,
+            fit_intercept: self.fit_intercept,
+        };
+
+        let initial = Array2::zeros((noutputs, nfeatures + 1));
+
+        let params = match self.solver {
+            Solver::Lbfgs { memory } => {
+                if l1 > 0. || memory == 0 {
+                    return None;
+                }
+
+                lbfgs(&problem, initial, memory, self.tol, self.max_iter)
+            }
+            Solver::Sgd {
+                learning_rate,
+                batch_size,
+                seed,
+            } => {
+                if learning_rate <= 0. || batch_size == 0 {
+                    return None;
+                }
+
+                sgd(
+                    &problem,
+                    initial,
+                    learning_rate,
+                    batch_size,
+                    seed,
+                    self.tol,
+                    self.max_iter,
+                )
+            }
+        };
+
+        if params.iter().any(|v| !v.is_finite()) {
+            return None;
+        }
+
+        Some(LogisticRegression {
+            _input: PhantomData,
+            coefficients: params.slice(s![.., ..nfeatures]).to_owned(),
+            intercepts: params.column(nfeatures).to_owned(),
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label>
+    for LogisticRegression<Input, Label>
+{
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.coefficients.ncols(),
+        )?;
+
+        let scores = features.dot(&self.coefficients.t()) + &self.intercepts;
+
+        match self.labels.len() {
+            2 => {
+                let positive = scores.column(0).mapv(sigmoid);
+                let mut proba = Array2::zeros((features.nrows(), 2));
+
+                proba.column_mut(0).assign(&(1. - &positive));
+                proba.column_mut(1).assign(&positive);
+
+                Some(proba)
+            }
+            _ => Some(softmax(scores)),
+        }
+    }
+}
@@ -1,8 +1,19 @@
 //! Commonly used classification models.
 
-use ndarray::Array2;
+use ndarray::{Array1, Array2};
+use rand::{rngs::StdRng, seq::SliceRandom};
 
+use crate::{argmax, Estimatable};
+
+pub mod calibration;
+pub mod discriminant_analysis;
+pub mod dummy;
+pub mod logistic;
+pub mod multiclass;
+pub mod multilabel;
 pub mod naive_bayes;
+pub mod neighbors;
+pub mod svm;
 
 /// Single training record for classification task
 #[derive(Debug)]
@@ -80,6 +91,192 @@ impl<Features, Label> ClassificationDataSet<Features, Label> {
     }
 }
 
+impl<Features, Label: PartialEq + Clone> ClassificationDataSet<Features, Label> {
+    /// Distinct labels in the dataset, in order of first appearance.
+    pub(crate) fn distinct_labels(&self) -> Vec<Label> {
+        self.get_labels().into_iter().fold(vec![], |mut agg, curr| {
+            if !agg.contains(curr) {
+                agg.push(curr.clone());
+            }
+            agg
+        })
+    }
+
+    /// Indices of the records belonging to each of the given labels.
+    pub(crate) fn label_indices(&self, labels: &[Label]) -> Vec<Vec<usize>> {
+        labels
+            .iter()
+            .map(|label| {
+                self.get_labels()
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(idx, l)| match l == label {
+                        true => Some(idx),
+                        false => None,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Index into the given labels of the label of each record.
+    pub(crate) fn label_classes(&self, labels: &[Label]) -> Vec<usize> {
+        let mut classes = vec![0; self.get_labels().len()];
+
+        for (idx, indeces) in self.label_indices(labels).iter().enumerate() {
+            for i in indeces {
+                classes[*i] = idx;
+            }
+        }
+
+        classes
+    }
+}
+
+impl<Features: Clone, Label> ClassificationDataSet<Features, Label> {
+    /// Copy of the records at the given indices, with their labels mapped by `relabel`.
+    pub(crate) fn select<L, F>(
+        &self,
+        indices: &[usize],
+        relabel: F,
+    ) -> ClassificationDataSet<Features, L>
+    where
+        F: Fn(&Label) -> L,
+    {
+        ClassificationDataSet::from(indices.iter().map(|idx| {
+            let record = &self.dataset[*idx];
+
+            (record.features.clone(), relabel(&record.label))
+        }))
+    }
+}
+
+impl<Features: Estimatable, Label> ClassificationDataSet<Features, Label> {
+    /// Features of all records stacked into a matrix, one row per record.
+    pub(crate) fn features_array(&self) -> Option<Array2<f64>> {
+        let rows: Vec<Array1<f64>> = self
+            .get_features()
+            .iter()
+            .map(|i| i.prepare_for_estimation())
+            .collect();
+
+        let nfeatures = rows.first()?.len();
+
+        to_feature_matrix(rows.into_iter(), nfeatures)
+    }
+}
+
+/// Stack rows of prepared features into a matrix. Returns None if any row does not have
+/// `nfeatures` elements.
+pub(crate) fn to_feature_matrix<I>(rows: I, nfeatures: usize) -> Option<Array2<f64>>
+where
+    I: Iterator<Item = Array1<f64>>,
+{
+    let mut nrows = 0;
+    let mut flat = vec![];
+
+    for row in rows {
+        if row.len() != nfeatures {
+            return None;
+        }
+
+        flat.extend(row);
+        nrows += 1;
+    }
+
+    Array2::from_shape_vec((nrows, nfeatures), flat).ok()
+}
+
+/// Split record indices into training and validation indices, holding out a fraction of the
+/// records of each label. At least one record of each label is kept for training.
+pub(crate) fn stratified_split(
+    label_indices: &[Vec<usize>],
+    fraction: f64,
+    rng: &mut StdRng,
+) -> (Vec<usize>, Vec<usize>) {
+    let mut train = vec![];
+    let mut validation = vec![];
+
+    for indeces in label_indices {
+        let mut indeces = indeces.clone();
+        indeces.shuffle(rng);
+
+        let nvalidation = (fraction * indeces.len() as f64).round() as usize;
+        let nvalidation = nvalidation.min(indeces.len().saturating_sub(1));
+
+        validation.extend_from_slice(&indeces[..nvalidation]);
+        train.extend_from_slice(&indeces[nvalidation..]);
+    }
+
+    (train, validation)
+}
+
+/// Split record indices into `nfolds` folds, dealing the shuffled records of each label out in
+/// turn so every fold holds a similar share of each label.
+pub(crate) fn stratified_folds(
+    label_indices: &[Vec<usize>],
+    nfolds: usize,
+    rng: &mut StdRng,
+) -> Vec<Vec<usize>> {
+    let mut folds = vec![vec![]; nfolds];
+    let mut next = 0;
+
+    for indeces in label_indices {
+        let mut indeces = indeces.clone();
+        indeces.shuffle(rng);
+
+        for idx in indeces {
+            folds[next % nfolds].push(idx);
+            next += 1;
+        }
+    }
+
+    folds
+}
+
+/// Record indices of all folds but one, in ascending order.
+pub(crate) fn fold_complement(folds: &[Vec<usize>], fold: usize) -> Vec<usize> {
+    let mut indeces: Vec<usize> = folds
+        .iter()
+        .enumerate()
+        .filter(|(other, _)| *other != fold)
+        .flat_map(|(_, indeces)| indeces.iter().copied())
+        .collect();
+    indeces.sort_unstable();
+
+    indeces
+}
+
+/// Probability of `true` per record of a binary classifier, zero if it was not fitted on `true`.
+pub(crate) fn positive_proba<F, C>(classifier: &C, features: &[F]) -> Option<Vec<f64>>
+where
+    F: Clone,
+    C: Classifier<F, bool>,
+{
+    let proba = classifier.predict_proba(features.iter().cloned())?;
+
+    match classifier.labels().iter().position(|label| *label) {
+        Some(column) => Some(proba.column(column).to_vec()),
+        None => Some(vec![0.; features.len()]),
+    }
+}
+
+/// Normalize joint log likelihoods per row into log probabilities using log-sum-exp.
+pub(crate) fn log_softmax(mut jll: Array2<f64>) -> Array2<f64> {
+    for mut row in jll.rows_mut() {
+        let max = row.fold(f64::NEG_INFINITY, |agg, curr| agg.max(*curr));
+        let log_norm = max + row.mapv(|v| (v - max).exp()).sum().ln();
+        row -= log_norm;
+    }
+
+    jll
+}
+
+/// Normalize joint log likelihoods per row into probabilities.
+pub(crate) fn softmax(jll: Array2<f64>) -> Array2<f64> {
+    log_softmax(jll).exp()
+}
+
 /// Trait to interface with a fitted classification model
 pub trait Classifier<Features, Label>
 where
@@ -95,7 +292,7 @@ where
         I: Iterator<Item = Features>;
 
     /// Provided function which returns the most likely class per record based on the results of
-    /// `predict_proba()`.
+    /// `predict_proba()`. Returns None if the model has no labels.
     fn predict<I>(&self, arr: I) -> Option<Vec<Label>>
     where
         I: Iterator<Item = Features>,
@@ -103,19 +300,92 @@ where
         let l = self.labels();
         let predictions = self.predict_proba(arr)?;
 
-        let a = predictions
+        predictions
             .rows()
             .into_iter()
-            .map(|a| {
-                a.iter().zip(l).fold((f64::MIN, l[0].clone()), |agg, curr| {
-                    match &agg.0 < curr.0 {
-                        true => (*curr.0, curr.1.clone()),
-                        false => agg,
-                    }
-                })
+            .map(|row| l.get(argmax(row.iter().copied())?).cloned())
+            .collect()
+    }
+
+    /// Provided function which predicts `positive` when its probability is at least `threshold`,
+    /// and the other label otherwise. Returns None if the model does not have exactly two labels
+    /// or was not fitted on `positive`.
+    fn predict_with_threshold<I>(
+        &self,
+        arr: I,
+        positive: &Label,
+        threshold: f64,
+    ) -> Option<Vec<Label>>
+    where
+        I: Iterator<Item = Features>,
+        Label: PartialEq,
+    {
+        let labels @ [_, _] = self.labels() else {
+            return None;
+        };
+
+        let column = labels.iter().position(|label| label == positive)?;
+        let negative = &labels[1 - column];
+
+        let predictions = self.predict_proba(arr)?;
+
+        let labels = predictions
+            .column(column)
+            .iter()
+            .map(|p| match *p >= threshold {
+                true => positive.clone(),
+                false => negative.clone(),
             })
-            .map(|(_, l)| l);
+            .collect();
+
+        Some(labels)
+    }
+
+    /// Provided function which returns the label with the lowest expected cost per record.
+    /// `costs[(i, j)]` is the cost of predicting label `j` for a record of label `i`, in the order
+    /// of `labels()`. Returns None if `costs` is not square with a row per label.
+    fn predict_with_costs<I>(&self, arr: I, costs: &Array2<f64>) -> Option<Vec<Label>>
+    where
+        I: Iterator<Item = Features>,
+    {
+        let l = self.labels();
+
+        if costs.dim() != (l.len(), l.len()) {
+            return None;
+        }
+
+        let expected_costs = self.predict_proba(arr)?.dot(costs);
+
+        expected_costs
+            .rows()
+            .into_iter()
+            .map(|row| l.get(argmax(row.iter().map(|cost| -cost))?).cloned())
+            .collect()
+    }
+
+    /// Provided function which returns up to `k` most likely labels per record with their
+    /// probabilities, from most to least likely. Labels with equal probabilities are in the order
+    /// of `labels()`.
+    fn predict_top_k<I>(&self, arr: I, k: usize) -> Option<Vec<Vec<(Label, f64)>>>
+    where
+        I: Iterator<Item = Features>,
+    {
+        let l = self.labels();
+        let predictions = self.predict_proba(arr)?;
+
+        let top_k = predictions
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let mut ranked: Vec<(Label, f64)> =
+                    l.iter().cloned().zip(row.iter().copied()).collect();
+                ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+                ranked.truncate(k);
+
+                ranked
+            })
+            .collect();
 
-        Some(a.collect())
+        Some(top_k)
     }
 }
@@ -0,0 +1,330 @@
+//! Meta-classifiers extending binary classifiers to any number of labels
+
+use ndarray::Array2;
+
+use crate::{argmax, Estimator};
+
+use super::{positive_proba, ClassificationDataSet, Classifier};
+
+/// Estimator fitting one binary classifier per label, separating the records with that label
+/// (`true`) from all other records (`false`). Two labels are separated by a single classifier for
+/// the second label.
+///
+/// The probability of each label is the probability of `true` of its classifier, normalized to
+/// sum to one per record. Fitting fails if any binary classifier fails to fit.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::logistic::LogisticRegressionEstimator;
+/// use rs_ml::classification::multiclass::OneVsRestEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 0.]), "origin"),
+///     (arr1(&[0.5, 0.]), "origin"),
+///     (arr1(&[5., 0.]), "east"),
+///     (arr1(&[5.5, 0.5]), "east"),
+///     (arr1(&[0., 5.]), "north"),
+///     (arr1(&[0.5, 5.5]), "north"),
+/// ]);
+///
+/// let model = OneVsRestEstimator::new(LogisticRegressionEstimator::default())
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[5., 1.]), arr1(&[1., 5.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["east", "north"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct OneVsRestEstimator<E> {
+    estimator: E,
+}
+
+/// Represents a fitted one-vs-rest classifier. Created with the `fit()` function implemented for
+/// [`OneVsRestEstimator`].
+#[derive(Debug)]
+pub struct OneVsRest<C, Label> {
+    classifiers: Vec<C>,
+    labels: Vec<Label>,
+}
+
+/// Estimator fitting one binary classifier per pair of labels, on the records with either label.
+/// Each classifier predicts `true` for the later label of its pair, in order of first appearance.
+///
+/// Predictions are the labels winning the most pairwise votes, with ties broken by the summed
+/// pairwise probabilities. The probability of each label is the sum of its pairwise
+/// probabilities divided by the number of pairs, so the probabilities of each record sum to one.
+/// Fitting fails if there are fewer than two labels or any binary classifier fails to fit.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::multiclass::OneVsOneEstimator;
+/// use rs_ml::classification::svm::LinearSVCEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 0.]), "origin"),
+///     (arr1(&[0.5, 0.]), "origin"),
+///     (arr1(&[5., 0.]), "east"),
+///     (arr1(&[5.5, 0.5]), "east"),
+///     (arr1(&[0., 5.]), "north"),
+///     (arr1(&[0.5, 5.5]), "north"),
+/// ]);
+///
+/// let model = OneVsOneEstimator::new(LinearSVCEstimator::default().with_seed(Some(0)))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[5., 1.]), arr1(&[1., 5.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["east", "north"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct OneVsOneEstimator<E> {
+    estimator: E,
+}
+
+/// Represents a fitted one-vs-one classifier. Created with the `fit()` function implemented for
+/// [`OneVsOneEstimator`].
+#[derive(Debug)]
+pub struct OneVsOne<C, Label> {
+    classifiers: Vec<C>,
+    pairs: Vec<(usize, usize)>,
+    labels: Vec<Label>,
+}
+
+impl<E> OneVsRestEstimator<E> {
+    /// Create a new one-vs-rest estimator fitting binary classifiers with `estimator`.
+    pub fn new(estimator: E) -> Self {
+        OneVsRestEstimator { estimator }
+    }
+}
+
+impl<E> OneVsOneEstimator<E> {
+    /// Create a new one-vs-one estimator fitting binary classifiers with `estimator`.
+    pub fn new(estimator: E) -> Self {
+        OneVsOneEstimator { estimator }
+    }
+}
+
+impl<F, L, E> Estimator<ClassificationDataSet<F, L>> for OneVsRestEstimator<E>
+where
+    F: Clone,
+    L: PartialEq + Clone,
+    E: Estimator<ClassificationDataSet<F, bool>>,
+    E::Estimator: Classifier<F, bool>,
+{
+    type Estimator = OneVsRest<E::Estimator, L>;
+
+    fn fit(&self, input: &ClassificationDataSet<F, L>) -> Option<Self::Estimator> {
+        let distinct_labels = input.distinct_labels();
+        let all: Vec<usize> = (0..input.dataset.len()).collect();
+
+        let positives = match distinct_labels.len() {
+            2 => &distinct_labels[1..],
+            _ => &distinct_labels[..],
+        };
+
+        let classifiers = positives
+            .iter()
+            .map(|positive| {
+                self.estimator
+                    .fit(&input.select(&all, |label| label == positive))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(OneVsRest {
+            classifiers,
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<C, Label> OneVsRest<C, Label> {
+    /// Binary classifier of each label, or of the second label if there are two labels.
+    pub fn classifiers(&self) -> &[C] {
+        &self.classifiers
+    }
+}
+
+impl<F, L, C> Classifier<F, L> for OneVsRest<C, L>
+where
+    F: Clone,
+    L: Clone,
+    C: Classifier<F, bool>,
+{
+    fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = F>,
+    {
+        let features: Vec<F> = arr.collect();
+        let nlabels = self.labels.len();
+        let mut proba = Array2::zeros((features.len(), nlabels));
+
+        for (idx, classifier) in self.classifiers.iter().enumerate() {
+            let positive = positive_proba(classifier, &features)?;
+
+            match self.classifiers.len() {
+                1 if nlabels == 2 => {
+                    for (row, p) in positive.iter().enumerate() {
+                        proba[(row, 0)] = 1. - p;
+                        proba[(row, 1)] = *p;
+                    }
+                }
+                _ => proba
+                    .column_mut(idx)
+                    .assign(&ndarray::Array1::from(positive)),
+            }
+        }
+
+        for mut row in proba.rows_mut() {
+            let total = row.sum();
+
+            match total > 0. {
+                true => row /= total,
+                false => row.fill(1. / nlabels as f64),
+            }
+        }
+
+        Some(proba)
+    }
+}
+
+impl<F, L, E> Estimator<ClassificationDataSet<F, L>> for OneVsOneEstimator<E>
+where
+    F: Clone,
+    L: PartialEq + Clone,
+    E: Estimator<ClassificationDataSet<F, bool>>,
+    E::Estimator: Classifier<F, bool>,
+{
+    type Estimator = OneVsOne<E::Estimator, L>;
+
+    fn fit(&self, input: &ClassificationDataSet<F, L>) -> Option<Self::Estimator> {
+        let distinct_labels = input.distinct_labels();
+
+        if distinct_labels.len() < 2 {
+            return None;
+        }
+
+        let label_indices = input.label_indices(&distinct_labels);
+
+        let pairs: Vec<(usize, usize)> = (0..distinct_labels.len())
+            .flat_map(|first| {
+                ((first + 1)..distinct_labels.len()).map(move |second| (first, second))
+            })
+            .collect();
+
+        let classifiers = pairs
+            .iter()
+            .map(|(first, second)| {
+                let mut indices = [
+                    label_indices[*first].clone(),
+                    label_indices[*second].clone(),
+                ]
+                .concat();
+                indices.sort_unstable();
+
+                let positive = &distinct_labels[*second];
+
+                self.estimator
+                    .fit(&input.select(&indices, |label| label == positive))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(OneVsOne {
+            classifiers,
+            pairs,
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<C, Label> OneVsOne<C, Label> {
+    /// Binary classifier of each pair of labels.
+    pub fn classifiers(&self) -> &[C] {
+        &self.classifiers
+    }
+
+    /// Indices into the labels of the model of the pair each classifier separates. Classifiers
+    /// predict `true` for the second label of the pair.
+    pub fn pairs(&self) -> &[(usize, usize)] {
+        &self.pairs
+    }
+}
+
+impl<C, L> OneVsOne<C, L> {
+    /// Pairwise votes and summed pairwise probabilities of each label per record.
+    fn votes<F>(&self, features: &[F]) -> Option<(Array2<f64>, Array2<f64>)>
+    where
+        F: Clone,
+        C: Classifier<F, bool>,
+    {
+        let mut votes = Array2::zeros((features.len(), self.labels.len()));
+        let mut confidences = Array2::zeros((features.len(), self.labels.len()));
+
+        for (classifier, (first, second)) in self.classifiers.iter().zip(&self.pairs) {
+            for (row, p) in positive_proba(classifier, features)?.iter().enumerate() {
+                confidences[(row, *first)] += 1. - p;
+                confidences[(row, *second)] += p;
+
+                match *p > 0.5 {
+                    true => votes[(row, *second)] += 1.,
+                    false => votes[(row, *first)] += 1.,
+                }
+            }
+        }
+
+        Some((votes, confidences))
+    }
+}
+
+impl<F, L, C> Classifier<F, L> for OneVsOne<C, L>
+where
+    F: Clone,
+    L: Clone,
+    C: Classifier<F, bool>,
+{
+    fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = F>,
+    {
+        let features: Vec<F> = arr.collect();
+        let (_, confidences) = self.votes(&features)?;
+
+        match self.labels.len() {
+            nlabels @ (0 | 1) => Some(Array2::ones((features.len(), nlabels))),
+            nlabels => Some(confidences * 2. / (nlabels * (nlabels - 1)) as f64),
+        }
+    }
+
+    fn predict<I>(&self, arr: I) -> Option<Vec<L>>
+    where
+        I: Iterator<Item = F>,
+    {
+        let features: Vec<F> = arr.collect();
+        let (votes, confidences) = self.votes(&features)?;
+        let npairs = self.pairs.len().max(1) as f64;
+
+        // confidences are bounded by the number of pairs, so they only ever break ties in votes
+        let scores = votes + confidences / (npairs + 1.);
+
+        scores
+            .rows()
+            .into_iter()
+            .map(|row| self.labels.get(argmax(row.iter().copied())?).cloned())
+            .collect()
+    }
+}
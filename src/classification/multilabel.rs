@@ -0,0 +1,476 @@
+//! Classification of records tagged with any number of labels
+
+use std::marker::PhantomData;
+
+use ndarray::{concatenate, Array1, Array2, Axis};
+
+use crate::{Estimatable, Estimator};
+
+use super::{positive_proba, to_feature_matrix, ClassificationDataSet, Classifier};
+
+/// Single training record tagged with any number of labels.
+#[derive(Debug)]
+pub struct MultiLabelRecord<Features, Label> {
+    /// feature for a single multi-label record
+    pub features: Features,
+    /// labels for a single multi-label record
+    pub labels: Vec<Label>,
+}
+
+/// Dataset to feed into multi-label classification model for training task
+#[derive(Debug)]
+pub struct MultiLabelDataSet<Features, Label> {
+    /// dataset of multi-label records on which to train
+    pub dataset: Vec<MultiLabelRecord<Features, Label>>,
+}
+
+impl<Features, Label> From<(Features, Vec<Label>)> for MultiLabelRecord<Features, Label> {
+    fn from(value: (Features, Vec<Label>)) -> Self {
+        MultiLabelRecord {
+            features: value.0,
+            labels: value.1,
+        }
+    }
+}
+
+impl<Itr, Record, Features, Label> From<Itr> for MultiLabelDataSet<Features, Label>
+where
+    Itr: IntoIterator<Item = Record>,
+    Record: Into<MultiLabelRecord<Features, Label>>,
+{
+    fn from(value: Itr) -> Self {
+        MultiLabelDataSet {
+            dataset: value.into_iter().map(|record| record.into()).collect(),
+        }
+    }
+}
+
+impl<Features, Label> MultiLabelDataSet<Features, Label> {
+    /// get labels for record
+    pub fn get_labels(&self) -> Vec<&Vec<Label>> {
+        self.dataset.iter().map(|record| &record.labels).collect()
+    }
+
+    /// get features
+    pub fn get_features(&self) -> Vec<&Features> {
+        self.dataset.iter().map(|record| &record.features).collect()
+    }
+
+    /// get records
+    pub fn get_records(&self) -> &Vec<MultiLabelRecord<Features, Label>> {
+        &self.dataset
+    }
+}
+
+impl<Features, Label: PartialEq + Clone> MultiLabelDataSet<Features, Label> {
+    /// Labels of the records as an indicator matrix, with a row per record and a column per given
+    /// label, true if the record is tagged with the label.
+    pub fn indicator_matrix(&self, labels: &[Label]) -> Array2<bool> {
+        Array2::from_shape_fn((self.dataset.len(), labels.len()), |(row, column)| {
+            self.dataset[row].labels.contains(&labels[column])
+        })
+    }
+
+    /// Distinct labels in the dataset, in order of first appearance.
+    pub(crate) fn distinct_labels(&self) -> Vec<Label> {
+        self.dataset
+            .iter()
+            .flat_map(|record| &record.labels)
+            .fold(vec![], |mut agg, curr| {
+                if !agg.contains(curr) {
+                    agg.push(curr.clone());
+                }
+                agg
+            })
+    }
+}
+
+/// Trait to interface with a fitted multi-label classification model
+pub trait MultiLabelClassifier<Features, Label>
+where
+    Label: Clone,
+{
+    /// Labels on which the model is fitted.
+    fn labels(&self) -> &[Label];
+
+    /// Estimates likelihood of each label per record, independently of the other labels. Rows
+    /// correspond to each record, columns are in the same order as label function.
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Features>;
+
+    /// Provided function which returns an indicator matrix of the labels of each record, in the
+    /// same layout as `predict_proba()`. Labels are predicted when their likelihood is above 0.5.
+    fn predict<I>(&self, arr: I) -> Option<Array2<bool>>
+    where
+        I: Iterator<Item = Features>,
+    {
+        Some(self.predict_proba(arr)?.mapv(|p| p > 0.5))
+    }
+}
+
+/// Estimator fitting one binary classifier per label, separating the records tagged with the
+/// label (`true`) from all other records (`false`). Labels are predicted independently of each
+/// other, with the predictions of their binary classifiers.
+///
+/// Fitting fails if any binary classifier fails to fit, which some do for labels tagged on every
+/// record.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, arr2, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::multilabel::{
+///     BinaryRelevanceEstimator, MultiLabelClassifier, MultiLabelDataSet,
+/// };
+/// use rs_ml::classification::naive_bayes::GaussianNBEstimator;
+///
+/// let dataset: MultiLabelDataSet<Array1<f64>, &str> = MultiLabelDataSet::from(vec![
+///     (arr1(&[0., 0.]), vec![]),
+///     (arr1(&[0., 0.5]), vec![]),
+///     (arr1(&[5., 0.]), vec!["east"]),
+///     (arr1(&[5.5, 0.5]), vec!["east"]),
+///     (arr1(&[0., 5.]), vec!["north"]),
+///     (arr1(&[0.5, 5.5]), vec!["north"]),
+///     (arr1(&[5., 5.]), vec!["north", "east"]),
+///     (arr1(&[5.5, 5.5]), vec!["north", "east"]),
+/// ]);
+///
+/// let model = BinaryRelevanceEstimator::new(GaussianNBEstimator::default())
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[5., 0.5]), arr1(&[5., 5.])].into_iter()).unwrap();
+///
+/// assert_eq!(model.labels(), &["east", "north"]);
+/// assert_eq!(predictions, arr2(&[[true, false], [true, true]]));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BinaryRelevanceEstimator<E> {
+    estimator: E,
+}
+
+/// Represents a fitted binary relevance classifier. Created with the `fit()` function implemented
+/// for [`BinaryRelevanceEstimator`].
+#[derive(Debug)]
+pub struct BinaryRelevance<C, Label> {
+    classifiers: Vec<C>,
+    labels: Vec<Label>,
+}
+
+/// Estimator fitting one binary classifier per label in a chain, so correlations between labels
+/// can be learned. The features of each classifier are extended with indicators, one for true and
+/// zero for false, of the labels before it in the chain. Classifiers are fitted on the labels of
+/// the records, and predict with the predictions of the classifiers before them.
+///
+/// The chain follows the labels in order of first appearance, unless an order is set as the
+/// indices of the labels in that order.
+///
+/// Fitting fails if the order is not a permutation of the label indices, or any binary classifier
+/// fails to fit, which some do for labels tagged on every record.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, arr2, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::logistic::LogisticRegressionEstimator;
+/// use rs_ml::classification::multilabel::{
+///     ClassifierChainEstimator, MultiLabelClassifier, MultiLabelDataSet,
+/// };
+///
+/// let dataset: MultiLabelDataSet<Array1<f64>, &str> = MultiLabelDataSet::from(vec![
+///     (arr1(&[0., 0.]), vec![]),
+///     (arr1(&[0., 0.5]), vec![]),
+///     (arr1(&[5., 0.]), vec!["east"]),
+///     (arr1(&[5.5, 0.5]), vec!["east"]),
+///     (arr1(&[0., 5.]), vec!["north"]),
+///     (arr1(&[0.5, 5.5]), vec!["north"]),
+///     (arr1(&[5., 5.]), vec!["north", "east"]),
+///     (arr1(&[5.5, 5.5]), vec!["north", "east"]),
+/// ]);
+///
+/// let model = ClassifierChainEstimator::new(LogisticRegressionEstimator::default())
+///     .with_order(Some(vec![1, 0]))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[5., 0.5]), arr1(&[5., 5.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, arr2(&[[true, false], [true, true]]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClassifierChainEstimator<E> {
+    estimator: E,
+    order: Option<Vec<usize>>,
+}
+
+/// Represents a fitted classifier chain. Created with the `fit()` function implemented for
+/// [`ClassifierChainEstimator`].
+#[derive(Debug)]
+pub struct ClassifierChain<Input, C, Label> {
+    _input: PhantomData<Input>,
+    classifiers: Vec<C>,
+    order: Vec<usize>,
+    nfeatures: usize,
+    labels: Vec<Label>,
+}
+
+impl<E> BinaryRelevanceEstimator<E> {
+    /// Create a new binary relevance estimator fitting binary classifiers with `estimator`.
+    pub fn new(estimator: E) -> Self {
+        BinaryRelevanceEstimator { estimator }
+    }
+}
+
+impl<E> ClassifierChainEstimator<E> {
+    /// Create a new classifier chain estimator fitting binary classifiers with `estimator`.
+    pub fn new(estimator: E) -> Self {
+        ClassifierChainEstimator {
+            estimator,
+            order: None,
+        }
+    }
+
+    /// Set order of the chain as indices into the labels in order of first appearance, or None to
+    /// follow that order.
+    pub fn with_order(self, order: Option<Vec<usize>>) -> Self {
+        ClassifierChainEstimator { order, ..self }
+    }
+}
+
+impl<F, L, E> Estimator<MultiLabelDataSet<F, L>> for BinaryRelevanceEstimator<E>
+where
+    F: Clone,
+    L: PartialEq + Clone,
+    E: Estimator<ClassificationDataSet<F, bool>>,
+    E::Estimator: Classifier<F, bool>,
+{
+    type Estimator = BinaryRelevance<E::Estimator, L>;
+
+    fn fit(&self, input: &MultiLabelDataSet<F, L>) -> Option<Self::Estimator> {
+        let labels = input.distinct_labels();
+        let indicators = input.indicator_matrix(&labels);
+
+        let classifiers = indicators
+            .columns()
+            .into_iter()
+            .map(|column| {
+                let dataset = ClassificationDataSet::from(
+                    input
+                        .dataset
+                        .iter()
+                        .zip(column)
+                        .map(|(record, tagged)| (record.features.clone(), *tagged)),
+                );
+
+                self.estimator.fit(&dataset)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(BinaryRelevance {
+            classifiers,
+            labels,
+        })
+    }
+}
+
+impl<C, Label> BinaryRelevance<C, Label> {
+    /// Binary classifier of each label.
+    pub fn classifiers(&self) -> &[C] {
+        &self.classifiers
+    }
+}
+
+impl<F, L, C> MultiLabelClassifier<F, L> for BinaryRelevance<C, L>
+where
+    F: Clone,
+    L: Clone,
+    C: Classifier<F, bool>,
+{
+    fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = F>,
+    {
+        let features: Vec<F> = arr.collect();
+        let mut proba = Array2::zeros((features.len(), self.labels.len()));
+
+        for (mut column, classifier) in proba.columns_mut().into_iter().zip(&self.classifiers) {
+            column.assign(&Array1::from(positive_proba(classifier, &features)?));
+        }
+
+        Some(proba)
+    }
+
+    fn predict<I>(&self, arr: I) -> Option<Array2<bool>>
+    where
+        I: Iterator<Item = F>,
+    {
+        let features: Vec<F> = arr.collect();
+        let mut predictions = Array2::from_elem((features.len(), self.labels.len()), false);
+
+        for (mut column, classifier) in predictions.columns_mut().into_iter().zip(&self.classifiers)
+        {
+            column.assign(&Array1::from(classifier.predict(features.iter().cloned())?));
+        }
+
+        Some(predictions)
+    }
+}
+
+/// Features extended with one column per label indicator.
+fn extend_features(features: &Array2<f64>, indicators: &[Array1<f64>]) -> Option<Vec<Array1<f64>>> {
+    let columns: Vec<_> = indicators
+        .iter()
+        .map(|indicator| indicator.view().insert_axis(Axis(1)))
+        .collect();
+
+    let extended = concatenate(Axis(1), &[&[features.view()], columns.as_slice()].concat()).ok()?;
+
+    Some(
+        extended
+            .rows()
+            .into_iter()
+            .map(|row| row.to_owned())
+            .collect(),
+    )
+}
+
+impl<F, L, E> Estimator<MultiLabelDataSet<F, L>> for ClassifierChainEstimator<E>
+where
+    F: Estimatable,
+    L: PartialEq + Clone,
+    E: Estimator<ClassificationDataSet<Array1<f64>, bool>>,
+    E::Estimator: Classifier<Array1<f64>, bool>,
+{
+    type Estimator = ClassifierChain<F, E::Estimator, L>;
+
+    fn fit(&self, input: &MultiLabelDataSet<F, L>) -> Option<Self::Estimator> {
+        let labels = input.distinct_labels();
+        let indicators = input.indicator_matrix(&labels);
+
+        let order = match &self.order {
+            Some(order) => order.clone(),
+            None => (0..labels.len()).collect(),
+        };
+
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+
+        if sorted != (0..labels.len()).collect::<Vec<usize>>() {
+            return None;
+        }
+
+        let rows: Vec<Array1<f64>> = input
+            .get_features()
+            .iter()
+            .map(|features| features.prepare_for_estimation())
+            .collect();
+        let nfeatures = rows.first()?.len();
+        let features = to_feature_matrix(rows.into_iter(), nfeatures)?;
+
+        let mut previous: Vec<Array1<f64>> = vec![];
+        let mut classifiers = vec![];
+
+        for label in &order {
+            let column = indicators.column(*label);
+
+            let dataset = ClassificationDataSet::from(
+                extend_features(&features, &previous)?
+                    .into_iter()
+                    .zip(column.iter().copied()),
+            );
+
+            classifiers.push(self.estimator.fit(&dataset)?);
+            previous.push(column.mapv(|tagged| match tagged {
+                true => 1.,
+                false => 0.,
+            }));
+        }
+
+        Some(ClassifierChain {
+            _input: PhantomData,
+            classifiers,
+            order,
+            nfeatures,
+            labels,
+        })
+    }
+}
+
+impl<Input, C, Label> ClassifierChain<Input, C, Label> {
+    /// Binary classifier of each label, in the order of the chain.
+    pub fn classifiers(&self) -> &[C] {
+        &self.classifiers
+    }
+
+    /// Indices into the labels in the order of the chain.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+}
+
+impl<Input, C: Classifier<Array1<f64>, bool>, Label> ClassifierChain<Input, C, Label> {
+    /// Probabilities and predictions of each label per record, with the predictions of the
+    /// classifiers before each classifier in the chain.
+    fn chain(&self, features: &Array2<f64>) -> Option<(Array2<f64>, Array2<bool>)> {
+        let mut proba = Array2::zeros((features.nrows(), self.labels.len()));
+        let mut predictions = Array2::from_elem((features.nrows(), self.labels.len()), false);
+        let mut previous: Vec<Array1<f64>> = vec![];
+
+        for (classifier, label) in self.classifiers.iter().zip(&self.order) {
+            let extended = extend_features(features, &previous)?;
+            let predicted = Array1::from(classifier.predict(extended.iter().cloned())?);
+
+            proba
+                .column_mut(*label)
+                .assign(&Array1::from(positive_proba(classifier, &extended)?));
+            predictions.column_mut(*label).assign(&predicted);
+
+            previous.push(predicted.mapv(|tagged| match tagged {
+                true => 1.,
+                false => 0.,
+            }));
+        }
+
+        Some((proba, predictions))
+    }
+}
+
+impl<F, L, C> MultiLabelClassifier<F, L> for ClassifierChain<F, C, L>
+where
+    F: Estimatable,
+    L: Clone,
+    C: Classifier<Array1<f64>, bool>,
+{
+    fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = F>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.nfeatures,
+        )?;
+
+        Some(self.chain(&features)?.0)
+    }
+
+    fn predict<I>(&self, arr: I) -> Option<Array2<bool>>
+    where
+        I: Iterator<Item = F>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.nfeatures,
+        )?;
+
+        Some(self.chain(&features)?.1)
+    }
+}
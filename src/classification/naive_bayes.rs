@@ -1,14 +1,22 @@
 //! Naive Bayes classifiers
 
-use crate::{Axis, Estimatable, Estimator};
+use crate::{argmax, Axis, Estimatable, Estimator, WeightedEstimator};
 use core::f64;
 use ndarray::{Array1, Array2};
 use std::{f64::consts::PI, marker::PhantomData};
 
-use super::{ClassificationDataSet, Classifier};
+use super::{log_softmax, softmax, to_feature_matrix, ClassificationDataSet, Classifier};
 
 /// Estimator to train a [`GaussianNB`] classifier.
 ///
+/// The variance of each feature per class is the population variance, dividing by the number
+/// (or total weight) of records of the class rather than by one less, so that batches added with
+/// [`GaussianNB::partial_fit`] merge exactly with the fitted statistics.
+///
+/// `var_smoothing`, which defaults to `1e-9`, is the portion of the largest feature variance added
+/// to all variances, so features that are constant within a class do not cause a division by
+/// zero.
+///
 /// Example:
 /// ```
 /// use ndarray::{arr1, arr2};
@@ -33,93 +41,451 @@ use super::{ClassificationDataSet, Classifier};
 ///     .collect();
 ///
 /// let dataset = ClassificationDataSet::from(records);
-/// let model = GaussianNBEstimator.fit(&dataset).unwrap();
+/// let model = GaussianNBEstimator::default().fit(&dataset).unwrap();
 /// ```
-#[derive(Debug, Clone, Copy)]
-pub struct GaussianNBEstimator;
+#[derive(Debug, Clone)]
+pub struct GaussianNBEstimator<Label> {
+    var_smoothing: f64,
+    priors: Option<Vec<(Label, f64)>>,
+}
+
+impl<Label> Default for GaussianNBEstimator<Label> {
+    fn default() -> Self {
+        GaussianNBEstimator {
+            var_smoothing: 1e-9,
+            priors: None,
+        }
+    }
+}
+
+impl<Label> GaussianNBEstimator<Label> {
+    /// Create estimator with given variance smoothing.
+    pub fn new(var_smoothing: f64) -> Self {
+        GaussianNBEstimator {
+            var_smoothing,
+            priors: None,
+        }
+    }
+
+    /// Use the given prior probability per label instead of deriving priors from the label
+    /// frequencies in the training data. Priors must be non-negative and sum to one, and every
+    /// label in the training data must have a prior, otherwise fitting fails.
+    ///
+    /// ```
+    /// use ndarray::{arr1, Array1};
+    /// use rs_ml::Estimator;
+    /// use rs_ml::classification::ClassificationDataSet;
+    /// use rs_ml::classification::naive_bayes::GaussianNBEstimator;
+    ///
+    /// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+    ///     (arr1(&[0., 0.]), false),
+    ///     (arr1(&[0., 1.]), false),
+    ///     (arr1(&[1., 0.]), true),
+    ///     (arr1(&[1., 1.]), true),
+    /// ]);
+    ///
+    /// // fraud is far rarer in production than in the training sample.
+    /// let model = GaussianNBEstimator::default()
+    ///     .with_priors(vec![(true, 0.01), (false, 0.99)])
+    ///     .fit(&dataset)
+    ///     .unwrap();
+    /// ```
+    pub fn with_priors(self, priors: Vec<(Label, f64)>) -> Self {
+        GaussianNBEstimator {
+            priors: Some(priors),
+            ..self
+        }
+    }
+}
 
 /// Represents a fitted Gaussian Naive Bayes Classifier. Created with the `fit()` function implemented for [`GaussianNBEstimator`].
+///
+/// The model can be updated with new batches of records using [`GaussianNB::partial_fit`].
 #[derive(Debug)]
 pub struct GaussianNB<Input, Label> {
     _input: PhantomData<Input>,
     means: Array2<f64>,
     vars: Array2<f64>,
     priors: Array1<f64>,
+    class_counts: Array1<f64>,
+    epsilon: f64,
+    fixed_priors: Option<Vec<(Label, f64)>>,
     labels: Vec<Label>,
 }
 
-impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
-    for GaussianNBEstimator
-{
-    type Estimator = GaussianNB<Input, Label>;
+/// Estimator to train a [`MultinomialNB`] classifier on count features, such as word or event
+/// counts. Features are smoothed with an additive (Laplace/Lidstone) `alpha`, which defaults to
+/// `1.0`. Fitting fails if `alpha` is not positive or any feature is negative.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::naive_bayes::MultinomialNBEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[3., 0., 1.]), "sports"),
+///     (arr1(&[4., 1., 0.]), "sports"),
+///     (arr1(&[0., 5., 2.]), "politics"),
+///     (arr1(&[1., 3., 3.]), "politics"),
+/// ]);
+///
+/// let model = MultinomialNBEstimator::new(0.5).fit(&dataset).unwrap();
+/// let predictions = model.predict([arr1(&[5., 0., 0.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["sports"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MultinomialNBEstimator {
+    alpha: f64,
+}
 
-    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
-        let distinct_labels: Vec<_> =
-            input
-                .get_labels()
-                .into_iter()
-                .fold(vec![], |mut agg, curr| {
-                    if agg.contains(curr) {
-                        agg
-                    } else {
-                        agg.push(curr.clone());
-                        agg
-                    }
-                });
-
-        let features_vec: Vec<_> = input
-            .get_features()
-            .iter()
-            .map(|i| i.prepare_for_estimation())
-            .collect();
+impl Default for MultinomialNBEstimator {
+    fn default() -> Self {
+        MultinomialNBEstimator { alpha: 1.0 }
+    }
+}
 
-        let nrows = features_vec.len();
-        let nfeatures = features_vec.first()?.len();
+impl MultinomialNBEstimator {
+    /// Create estimator with additive smoothing parameter `alpha`.
+    pub fn new(alpha: f64) -> Self {
+        MultinomialNBEstimator { alpha }
+    }
+}
 
-        let flat_shapes: Vec<f64> = features_vec
-            .iter()
-            .flat_map(|record| record.into_iter())
-            .copied()
-            .collect();
+/// Represents a fitted Multinomial Naive Bayes Classifier. Created with the `fit()` function
+/// implemented for [`MultinomialNBEstimator`].
+#[derive(Debug)]
+pub struct MultinomialNB<Input, Label> {
+    _input: PhantomData<Input>,
+    feature_log_probs: Array2<f64>,
+    class_log_priors: Array1<f64>,
+    labels: Vec<Label>,
+}
+
+/// Estimator to train a [`BernoulliNB`] classifier on binary features. Unlike [`GaussianNB`],
+/// absent features are explicitly penalized.
+///
+/// Features strictly greater than the `binarize` threshold (`0.0` by default) are treated as
+/// present. If `binarize` is `None`, features are expected to already be `0.0` or `1.0`. Feature
+/// frequencies are smoothed with an additive `alpha`, which defaults to `1.0`. Fitting fails if
+/// `alpha` is not positive.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::naive_bayes::BernoulliNBEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+///     (arr1(&[1., 1., 0.]), true),
+///     (arr1(&[1., 0., 0.]), true),
+///     (arr1(&[0., 1., 1.]), false),
+///     (arr1(&[0., 0., 1.]), false),
+/// ]);
+///
+/// let model = BernoulliNBEstimator::new(1.0, Some(0.5)).fit(&dataset).unwrap();
+/// let predictions = model.predict([arr1(&[1., 0., 0.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec![true]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BernoulliNBEstimator {
+    alpha: f64,
+    binarize: Option<f64>,
+}
+
+impl Default for BernoulliNBEstimator {
+    fn default() -> Self {
+        BernoulliNBEstimator {
+            alpha: 1.0,
+            binarize: Some(0.0),
+        }
+    }
+}
+
+impl BernoulliNBEstimator {
+    /// Create estimator with additive smoothing parameter `alpha` and `binarize` threshold.
+    pub fn new(alpha: f64, binarize: Option<f64>) -> Self {
+        BernoulliNBEstimator { alpha, binarize }
+    }
+}
+
+/// Represents a fitted Bernoulli Naive Bayes Classifier. Created with the `fit()` function
+/// implemented for [`BernoulliNBEstimator`].
+#[derive(Debug)]
+pub struct BernoulliNB<Input, Label> {
+    _input: PhantomData<Input>,
+    binarize: Option<f64>,
+    feature_log_probs: Array2<f64>,
+    neg_feature_log_probs: Array2<f64>,
+    class_log_priors: Array1<f64>,
+    labels: Vec<Label>,
+}
+
+/// Estimator to train a [`CategoricalNB`] classifier on discrete features, such as enum
+/// discriminants, without one hot encoding them first.
+///
+/// Each feature must be a non-negative whole number identifying its category. Categories do not
+/// need to be contiguous: each feature only keeps a frequency table of the categories observed
+/// during training. Category frequencies are smoothed with an additive `alpha`, which defaults to
+/// `1.0`, so categories not observed during training still receive a small likelihood at
+/// prediction time. Fitting fails if `alpha` is not positive or any feature is not a valid
+/// category.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::naive_bayes::CategoricalNBEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 2.]), true),
+///     (arr1(&[0., 1.]), true),
+///     (arr1(&[1., 0.]), false),
+///     (arr1(&[2., 0.]), false),
+/// ]);
+///
+/// let model = CategoricalNBEstimator::default().fit(&dataset).unwrap();
+///
+/// // category 3 of the first feature was never observed during training
+/// let predictions = model.predict([arr1(&[3., 2.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec![true]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CategoricalNBEstimator {
+    alpha: f64,
+}
+
+impl Default for CategoricalNBEstimator {
+    fn default() -> Self {
+        CategoricalNBEstimator { alpha: 1.0 }
+    }
+}
+
+impl CategoricalNBEstimator {
+    /// Create estimator with additive smoothing parameter `alpha`.
+    pub fn new(alpha: f64) -> Self {
+        CategoricalNBEstimator { alpha }
+    }
+}
+
+/// Represents a fitted Categorical Naive Bayes Classifier. Created with the `fit()` function
+/// implemented for [`CategoricalNBEstimator`].
+#[derive(Debug)]
+pub struct CategoricalNB<Input, Label> {
+    _input: PhantomData<Input>,
+    categories: Vec<Vec<f64>>,
+    category_log_probs: Vec<Array2<f64>>,
+    unseen_log_probs: Array2<f64>,
+    class_log_priors: Array1<f64>,
+    labels: Vec<Label>,
+}
+
+/// Estimator to train a [`ComplementNB`] classifier on count features. Feature weights are
+/// estimated from all records *not* belonging to a class, which makes the model more robust
+/// than [`MultinomialNB`] on imbalanced labels.
+///
+/// Counts are smoothed with an additive `alpha`, which defaults to `1.0`. If `norm` is set, the
+/// weights of each class are normalized to sum to one, which reduces the influence of longer
+/// records. Fitting fails if `alpha` is not positive or any feature is negative.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::naive_bayes::ComplementNBEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[3., 0., 1.]), "ham"),
+///     (arr1(&[4., 1., 0.]), "ham"),
+///     (arr1(&[2., 0., 0.]), "ham"),
+///     (arr1(&[0., 5., 2.]), "spam"),
+/// ]);
+///
+/// let model = ComplementNBEstimator::new(1.0, true).fit(&dataset).unwrap();
+/// let predictions = model.predict([arr1(&[0., 3., 1.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["spam"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ComplementNBEstimator {
+    alpha: f64,
+    norm: bool,
+}
+
+impl Default for ComplementNBEstimator {
+    fn default() -> Self {
+        ComplementNBEstimator {
+            alpha: 1.0,
+            norm: false,
+        }
+    }
+}
+
+impl ComplementNBEstimator {
+    /// Create estimator with additive smoothing parameter `alpha`, optionally normalizing the
+    /// feature weights of each class.
+    pub fn new(alpha: f64, norm: bool) -> Self {
+        ComplementNBEstimator { alpha, norm }
+    }
+}
+
+/// Represents a fitted Complement Naive Bayes Classifier. Created with the `fit()` function
+/// implemented for [`ComplementNBEstimator`].
+#[derive(Debug)]
+pub struct ComplementNB<Input, Label> {
+    _input: PhantomData<Input>,
+    feature_weights: Array2<f64>,
+    class_log_priors: Array1<f64>,
+    labels: Vec<Label>,
+}
+
+/// Total weight, weighted mean and weighted sum of squared deviations per feature. Returns None if
+/// the total weight is not positive.
+fn weighted_moments(
+    features: &Array2<f64>,
+    weights: &Array1<f64>,
+) -> Option<(f64, Array1<f64>, Array1<f64>)> {
+    let total = weights.sum();
 
-        let features = Array2::from_shape_vec((nrows, nfeatures), flat_shapes).ok()?;
+    if total <= 0. {
+        return None;
+    }
+
+    let mean = weights.dot(features) / total;
+    let m2 = weights.dot(&(features - &mean).pow2());
+
+    Some((total, mean, m2))
+}
+
+/// Sample weights as an array, or None if the weights do not match the number of records or any
+/// weight is negative.
+fn validate_weights(sample_weights: &[f64], nrows: usize) -> Option<Array1<f64>> {
+    match sample_weights.len() == nrows && sample_weights.iter().all(|w| *w >= 0.) {
+        true => Some(Array1::from(sample_weights.to_vec())),
+        false => None,
+    }
+}
+
+/// Prior of each label looked up from user supplied priors.
+fn lookup_priors<Label: PartialEq>(
+    labels: &[Label],
+    priors: &[(Label, f64)],
+) -> Option<Array1<f64>> {
+    labels
+        .iter()
+        .map(|label| {
+            priors
+                .iter()
+                .find(|(l, _)| l == label)
+                .map(|(_, prior)| *prior)
+        })
+        .collect()
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone>
+    WeightedEstimator<ClassificationDataSet<Input, Label>> for GaussianNBEstimator<Label>
+{
+    /// Fit a [`GaussianNB`] classifier with a weight per record. Means and variances of each
+    /// class are weighted, and unless priors are supplied, so are the class priors.
+    ///
+    /// Returns None if the number of weights does not match the number of records, any weight is
+    /// negative, or the records of a label have no total weight.
+    ///
+    /// ```
+    /// use ndarray::{arr1, Array1};
+    /// use rs_ml::classification::ClassificationDataSet;
+    /// use rs_ml::classification::naive_bayes::GaussianNBEstimator;
+    /// use rs_ml::WeightedEstimator;
+    ///
+    /// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+    ///     (arr1(&[0., 0.]), false),
+    ///     (arr1(&[0., 1.]), false),
+    ///     (arr1(&[1., 0.]), true),
+    ///     (arr1(&[1., 1.]), true),
+    /// ]);
+    ///
+    /// let model = GaussianNBEstimator::default()
+    ///     .fit_weighted(&dataset, &[1., 1., 0.5, 2.])
+    ///     .unwrap();
+    /// ```
+    fn fit_weighted(
+        &self,
+        input: &ClassificationDataSet<Input, Label>,
+        sample_weights: &[f64],
+    ) -> Option<GaussianNB<Input, Label>> {
+        let distinct_labels = input.distinct_labels();
+        let features = input.features_array()?;
+        let weights = validate_weights(sample_weights, features.nrows())?;
+
+        let nfeatures = features.ncols();
 
         let mut means = Array2::zeros((distinct_labels.len(), nfeatures));
         let mut vars = Array2::zeros((distinct_labels.len(), nfeatures));
-        let mut priors = Array1::zeros(distinct_labels.len());
-
-        for (idx, label) in distinct_labels.iter().enumerate() {
-            let indeces: Vec<usize> = input
-                .get_labels()
-                .into_iter()
-                .enumerate()
-                .filter_map(|(idx, l)| match l == label {
-                    true => Some(idx),
-                    false => None,
-                })
-                .collect();
-
-            let filtered_view = features.select(Axis(0), &indeces);
-            let c = filtered_view.nrows();
-
-            means
-                .row_mut(idx)
-                .assign(&filtered_view.mean_axis(Axis(0))?);
-            vars.row_mut(idx)
-                .assign(&filtered_view.var_axis(Axis(0), 1.0));
-            priors[idx] = c as f64 / nrows as f64;
+        let mut class_counts = Array1::zeros(distinct_labels.len());
+
+        for (idx, indeces) in input.label_indices(&distinct_labels).iter().enumerate() {
+            let (total, mean, m2) = weighted_moments(
+                &features.select(Axis(0), indeces),
+                &weights.select(Axis(0), indeces),
+            )?;
+
+            means.row_mut(idx).assign(&mean);
+            vars.row_mut(idx).assign(&(m2 / total));
+            class_counts[idx] = total;
         }
 
+        let priors = match &self.priors {
+            Some(priors) => {
+                let total: f64 = priors.iter().map(|(_, prior)| prior).sum();
+
+                if priors.iter().any(|(_, prior)| *prior < 0.) || (total - 1.).abs() > 1e-6 {
+                    return None;
+                }
+
+                lookup_priors(&distinct_labels, priors)?
+            }
+            None => &class_counts / class_counts.sum(),
+        };
+
+        let max_var = features
+            .var_axis(Axis(0), 0.0)
+            .fold(0.0_f64, |agg, curr| agg.max(*curr));
+
+        let epsilon = match max_var > 0. {
+            true => self.var_smoothing * max_var,
+            false => self.var_smoothing,
+        };
+
         Some(GaussianNB {
             _input: PhantomData,
             labels: distinct_labels,
             means,
-            vars,
+            vars: vars + epsilon,
             priors,
+            class_counts,
+            epsilon,
+            fixed_priors: self.priors.clone(),
         })
     }
 }
 
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for GaussianNBEstimator<Label>
+{
+    type Estimator = GaussianNB<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        self.fit_weighted(input, &vec![1.; input.get_records().len()])
+    }
+}
+
 impl<Input: Estimatable, Label: Clone> Classifier<Input, Label> for GaussianNB<Input, Label> {
     fn labels(&self) -> &[Label] {
         &self.labels
@@ -129,26 +495,490 @@ impl<Input: Estimatable, Label: Clone> Classifier<Input, Label> for GaussianNB<I
     where
         I: Iterator<Item = Input>,
     {
-        let col_count = self.labels.len();
+        Some(self.predict_log_proba(arr)?.exp())
+    }
 
-        let likelihoods: Vec<_> = arr
-            .map(|record| {
-                let arr_record = record.prepare_for_estimation();
-                let mut log_likelihood = -0.5 * (&self.vars.view() * 2.0 * PI).ln();
-                log_likelihood =
-                    log_likelihood - 0.5 * ((arr_record - &self.means).pow2() / self.vars.view());
+    fn predict<I>(&self, arr: I) -> Option<Vec<Label>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        self.predict_log_proba(arr)?
+            .rows()
+            .into_iter()
+            .map(|row| self.labels.get(argmax(row.iter().copied())?).cloned())
+            .collect()
+    }
+}
+
+impl<Input, Label> GaussianNB<Input, Label> {
+    /// Mean of each feature per class. Rows are in the same order as the labels of the model.
+    pub fn means(&self) -> &Array2<f64> {
+        &self.means
+    }
+
+    /// Population variance of each feature per class, including variance smoothing. Rows are in
+    /// the same order as the labels of the model.
+    pub fn variances(&self) -> &Array2<f64> {
+        &self.vars
+    }
+}
+
+impl<Input: Estimatable, Label> GaussianNB<Input, Label> {
+    /// Estimates the log likelihood of each class per record. Rows correspond to each record,
+    /// columns are in the same order as the labels of the model.
+    ///
+    /// Log probabilities are normalized with log-sum-exp, so records far away from all classes
+    /// do not underflow to zero probability for every class.
+    pub fn predict_log_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.means.ncols(),
+        )?;
+
+        let normalization = -0.5 * (&self.vars * 2.0 * PI).ln().sum_axis(Axis(1));
+        let log_priors = self.priors.ln();
+
+        let mut jll = Array2::zeros((features.nrows(), self.means.nrows()));
 
-                log_likelihood = log_likelihood + self.priors.view().insert_axis(Axis(1)).ln();
+        for (mut jll_row, record) in jll.rows_mut().into_iter().zip(features.rows()) {
+            let distances = ((&record - &self.means).pow2() / &self.vars).sum_axis(Axis(1));
+            jll_row.assign(&(&normalization - 0.5 * distances + &log_priors));
+        }
 
-                let likelihood = log_likelihood.sum_axis(Axis(1)).exp().to_owned();
+        Some(log_softmax(jll))
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> GaussianNB<Input, Label> {
+    /// Update the fitted model with a new batch of records, without refitting on previously seen
+    /// records. Means and variances are merged with the running statistics of the model, and
+    /// labels first appearing in this batch are added to the model.
+    ///
+    /// The variance smoothing computed when the model was fitted is kept for later batches.
+    ///
+    /// Returns None and leaves the model untouched if the batch does not have the same number of
+    /// features as the model was fitted on.
+    ///
+    /// Example:
+    /// ```
+    /// use ndarray::{arr1, Array1};
+    /// use rs_ml::Estimator;
+    /// use rs_ml::classification::{ClassificationDataSet, Classifier};
+    /// use rs_ml::classification::naive_bayes::GaussianNBEstimator;
+    ///
+    /// let first: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+    ///     (arr1(&[0., 0.]), "a"),
+    ///     (arr1(&[0., 1.]), "a"),
+    /// ]);
+    ///
+    /// let second: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+    ///     (arr1(&[5., 5.]), "b"),
+    ///     (arr1(&[5., 6.]), "b"),
+    /// ]);
+    ///
+    /// let mut model = GaussianNBEstimator::default().fit(&first).unwrap();
+    /// model.partial_fit(&second).unwrap();
+    ///
+    /// assert_eq!(model.labels(), &["a", "b"]);
+    /// ```
+    pub fn partial_fit(&mut self, input: &ClassificationDataSet<Input, Label>) -> Option<()> {
+        self.partial_fit_weighted(input, &vec![1.; input.get_records().len()])
+    }
 
-                &likelihood / likelihood.sum()
-            })
-            .flat_map(|likelihoods| likelihoods.into_iter())
+    /// Update the fitted model with a new batch of records, with a weight per record. See
+    /// [`GaussianNB::partial_fit`] and [`WeightedEstimator::fit_weighted`].
+    ///
+    /// If the model was fitted with user supplied priors, a label first appearing in this batch
+    /// must have a prior as well, otherwise the model is left untouched and None is returned.
+    pub fn partial_fit_weighted(
+        &mut self,
+        input: &ClassificationDataSet<Input, Label>,
+        sample_weights: &[f64],
+    ) -> Option<()> {
+        if input.get_records().is_empty() {
+            return Some(());
+        }
+
+        let features = input.features_array()?;
+        let weights = validate_weights(sample_weights, features.nrows())?;
+
+        if features.ncols() != self.means.ncols() {
+            return None;
+        }
+
+        let mut labels = self.labels.clone();
+        let mut means = self.means.clone();
+        let mut vars = self.vars.clone();
+        let mut class_counts = self.class_counts.to_vec();
+
+        let batch_labels = input.distinct_labels();
+
+        for (label, indeces) in batch_labels.iter().zip(input.label_indices(&batch_labels)) {
+            let (n_batch, mean_batch, m2_batch) = weighted_moments(
+                &features.select(Axis(0), &indeces),
+                &weights.select(Axis(0), &indeces),
+            )?;
+
+            match labels.iter().position(|l| l == label) {
+                Some(idx) => {
+                    let n_prev = class_counts[idx];
+                    let m2_prev = (&vars.row(idx) - self.epsilon) * n_prev;
+
+                    let n = n_prev + n_batch;
+                    let delta = &mean_batch - &means.row(idx);
+                    let m2 = m2_prev + m2_batch + delta.pow2() * (n_prev * n_batch / n);
+
+                    let updated_mean = &means.row(idx) + &(delta * (n_batch / n));
+                    means.row_mut(idx).assign(&updated_mean);
+                    vars.row_mut(idx).assign(&(m2 / n + self.epsilon));
+                    class_counts[idx] = n;
+                }
+                None => {
+                    labels.push(label.clone());
+                    means.push_row(mean_batch.view()).ok()?;
+                    vars.push_row((m2_batch / n_batch + self.epsilon).view())
+                        .ok()?;
+                    class_counts.push(n_batch);
+                }
+            }
+        }
+
+        let class_counts = Array1::from(class_counts);
+
+        self.priors = match &self.fixed_priors {
+            Some(priors) => lookup_priors(&labels, priors)?,
+            None => &class_counts / class_counts.sum(),
+        };
+        self.labels = labels;
+        self.means = means;
+        self.vars = vars;
+        self.class_counts = class_counts;
+
+        Some(())
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for MultinomialNBEstimator
+{
+    type Estimator = MultinomialNB<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if self.alpha <= 0. {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+        let features = input.features_array()?;
+
+        if features.iter().any(|v| *v < 0.) {
+            return None;
+        }
+
+        let nrows = features.nrows();
+        let nfeatures = features.ncols();
+
+        let mut feature_log_probs = Array2::zeros((distinct_labels.len(), nfeatures));
+        let mut class_log_priors = Array1::zeros(distinct_labels.len());
+
+        for (idx, indeces) in input.label_indices(&distinct_labels).iter().enumerate() {
+            let smoothed_counts = features.select(Axis(0), indeces).sum_axis(Axis(0)) + self.alpha;
+            let total = smoothed_counts.sum();
+
+            feature_log_probs
+                .row_mut(idx)
+                .assign(&(smoothed_counts.ln() - total.ln()));
+            class_log_priors[idx] = (indeces.len() as f64 / nrows as f64).ln();
+        }
+
+        Some(MultinomialNB {
+            _input: PhantomData,
+            labels: distinct_labels,
+            feature_log_probs,
+            class_log_priors,
+        })
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label> for MultinomialNB<Input, Label> {
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.feature_log_probs.ncols(),
+        )?;
+
+        let jll = features.dot(&self.feature_log_probs.t()) + &self.class_log_priors;
+
+        Some(softmax(jll))
+    }
+}
+
+/// Map features to 0 or 1 based on an optional threshold.
+fn binarize_features(features: Array2<f64>, threshold: Option<f64>) -> Array2<f64> {
+    match threshold {
+        Some(threshold) => features.mapv(|v| match v > threshold {
+            true => 1.,
+            false => 0.,
+        }),
+        None => features,
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for BernoulliNBEstimator
+{
+    type Estimator = BernoulliNB<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if self.alpha <= 0. {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+        let features = binarize_features(input.features_array()?, self.binarize);
+
+        let nrows = features.nrows();
+        let nfeatures = features.ncols();
+
+        let mut feature_log_probs = Array2::zeros((distinct_labels.len(), nfeatures));
+        let mut neg_feature_log_probs = Array2::zeros((distinct_labels.len(), nfeatures));
+        let mut class_log_priors = Array1::zeros(distinct_labels.len());
+
+        for (idx, indeces) in input.label_indices(&distinct_labels).iter().enumerate() {
+            let c = indeces.len() as f64;
+            let probs = (features.select(Axis(0), indeces).sum_axis(Axis(0)) + self.alpha)
+                / (c + 2. * self.alpha);
+
+            feature_log_probs.row_mut(idx).assign(&probs.ln());
+            neg_feature_log_probs
+                .row_mut(idx)
+                .assign(&(1. - probs).ln());
+            class_log_priors[idx] = (c / nrows as f64).ln();
+        }
+
+        Some(BernoulliNB {
+            _input: PhantomData,
+            binarize: self.binarize,
+            labels: distinct_labels,
+            feature_log_probs,
+            neg_feature_log_probs,
+            class_log_priors,
+        })
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label> for BernoulliNB<Input, Label> {
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = binarize_features(
+            to_feature_matrix(
+                arr.map(|record| record.prepare_for_estimation()),
+                self.feature_log_probs.ncols(),
+            )?,
+            self.binarize,
+        );
+
+        let jll = features.dot(&(&self.feature_log_probs - &self.neg_feature_log_probs).t())
+            + self.neg_feature_log_probs.sum_axis(Axis(1))
+            + &self.class_log_priors;
+
+        Some(softmax(jll))
+    }
+}
+
+/// Whether a feature value identifies a category, i.e. is a non-negative whole number.
+fn is_category(value: f64) -> bool {
+    value >= 0. && value.fract() == 0.
+}
+
+/// Index of a category within the sorted categories observed for a feature.
+fn category_index(categories: &[f64], value: f64) -> Option<usize> {
+    categories.binary_search_by(|c| c.total_cmp(&value)).ok()
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for CategoricalNBEstimator
+{
+    type Estimator = CategoricalNB<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if self.alpha <= 0. {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+        let features = input.features_array()?;
+
+        if features.iter().any(|v| !is_category(*v)) {
+            return None;
+        }
+
+        let nrows = features.nrows();
+        let nfeatures = features.ncols();
+        let label_indices = input.label_indices(&distinct_labels);
+
+        let mut categories = Vec::with_capacity(nfeatures);
+        let mut category_log_probs = Vec::with_capacity(nfeatures);
+        let mut unseen_log_probs = Array2::zeros((distinct_labels.len(), nfeatures));
+
+        for (feature, column) in features.columns().into_iter().enumerate() {
+            let mut observed = column.to_vec();
+            observed.sort_by(|a, b| a.total_cmp(b));
+            observed.dedup();
+
+            let mut log_probs = Array2::zeros((distinct_labels.len(), observed.len()));
+
+            for (idx, indeces) in label_indices.iter().enumerate() {
+                let mut counts = Array1::from_elem(observed.len(), self.alpha);
+
+                for i in indeces {
+                    counts[category_index(&observed, column[*i])?] += 1.;
+                }
+
+                let total = counts.sum();
+
+                log_probs.row_mut(idx).assign(&(counts.ln() - total.ln()));
+                unseen_log_probs[(idx, feature)] = self.alpha.ln() - total.ln();
+            }
+
+            categories.push(observed);
+            category_log_probs.push(log_probs);
+        }
+
+        let class_log_priors = label_indices
+            .iter()
+            .map(|indeces| (indeces.len() as f64 / nrows as f64).ln())
             .collect();
 
-        let row_count = likelihoods.len() / col_count;
+        Some(CategoricalNB {
+            _input: PhantomData,
+            labels: distinct_labels,
+            categories,
+            category_log_probs,
+            unseen_log_probs,
+            class_log_priors,
+        })
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label> for CategoricalNB<Input, Label> {
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.category_log_probs.len(),
+        )?;
+
+        let mut jll = Array2::zeros((features.nrows(), self.labels.len()));
+
+        for (mut jll_row, record) in jll.rows_mut().into_iter().zip(features.rows()) {
+            jll_row.assign(&self.class_log_priors);
+
+            for (feature, value) in record.iter().enumerate() {
+                if !is_category(*value) {
+                    return None;
+                }
+
+                match category_index(&self.categories[feature], *value) {
+                    Some(c) => jll_row += &self.category_log_probs[feature].column(c),
+                    None => jll_row += &self.unseen_log_probs.column(feature),
+                }
+            }
+        }
+
+        Some(softmax(jll))
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for ComplementNBEstimator
+{
+    type Estimator = ComplementNB<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if self.alpha <= 0. {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+        let features = input.features_array()?;
+
+        if features.iter().any(|v| *v < 0.) {
+            return None;
+        }
+
+        let nrows = features.nrows();
+        let total_counts = features.sum_axis(Axis(0));
+
+        let mut feature_weights = Array2::zeros((distinct_labels.len(), features.ncols()));
+        let mut class_log_priors = Array1::zeros(distinct_labels.len());
+
+        for (idx, indeces) in input.label_indices(&distinct_labels).iter().enumerate() {
+            let complement_counts =
+                &total_counts - features.select(Axis(0), indeces).sum_axis(Axis(0)) + self.alpha;
+            let logged = complement_counts.ln() - complement_counts.sum().ln();
+
+            let weights = match self.norm {
+                true => &logged / logged.sum(),
+                false => -logged,
+            };
+
+            feature_weights.row_mut(idx).assign(&weights);
+            class_log_priors[idx] = (indeces.len() as f64 / nrows as f64).ln();
+        }
+
+        Some(ComplementNB {
+            _input: PhantomData,
+            labels: distinct_labels,
+            feature_weights,
+            class_log_priors,
+        })
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label> for ComplementNB<Input, Label> {
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.feature_weights.ncols(),
+        )?;
+
+        let mut jll = features.dot(&self.feature_weights.t());
+
+        // the complement of the only class is empty, so fall back to its prior.
+        if self.labels.len() == 1 {
+            jll += &self.class_log_priors;
+        }
 
-        Array2::from_shape_vec((row_count, col_count), likelihoods).ok()
+        Some(softmax(jll))
     }
 }
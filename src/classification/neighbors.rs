@@ -0,0 +1,700 @@
+//! Nearest neighbour classifiers
+
+use std::{cmp::Ordering, collections::BinaryHeap, marker::PhantomData};
+
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+
+use crate::{quantile, Estimatable, Estimator};
+
+use super::{softmax, to_feature_matrix, ClassificationDataSet, Classifier};
+
+/// Distance metric between two records.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+    /// Straight line distance.
+    Euclidean,
+    /// Sum of absolute differences per feature.
+    Manhattan,
+    /// One minus the cosine similarity. Records with only zero features are at distance one of
+    /// every other record.
+    Cosine,
+    /// Generalization of euclidean and manhattan distance with power `p`, which must be at least
+    /// one.
+    Minkowski(f64),
+}
+
+impl Metric {
+    /// Distance between two records.
+    pub fn distance(&self, a: ArrayView1<f64>, b: ArrayView1<f64>) -> f64 {
+        match self {
+            Metric::Euclidean => a
+                .iter()
+                .zip(b.iter())
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f64>()
+                .sqrt(),
+            Metric::Manhattan => a.iter().zip(b.iter()).map(|(a, b)| (a - b).abs()).sum(),
+            Metric::Cosine => {
+                let norms = a.dot(&a).sqrt() * b.dot(&b).sqrt();
+
+                // rounding puts parallel records slightly below zero
+                match norms > 0. {
+                    true => (1. - a.dot(&b) / norms).clamp(0., 2.),
+                    false => 1.,
+                }
+            }
+            Metric::Minkowski(p) => a
+                .iter()
+                .zip(b.iter())
+                .map(|(a, b)| (a - b).abs().powf(*p))
+                .sum::<f64>()
+                .powf(p.recip()),
+        }
+    }
+
+    /// Whether the difference in any single feature is a lower bound of the distance, which is
+    /// required to prune a KD-tree.
+    fn is_coordinate_bounded(&self) -> bool {
+        match self {
+            Metric::Euclidean | Metric::Manhattan => true,
+            Metric::Cosine => false,
+            Metric::Minkowski(p) => *p >= 1.,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            Metric::Minkowski(p) => *p >= 1.,
+            _ => true,
+        }
+    }
+}
+
+/// How the neighbours of a record vote on its label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weights {
+    /// Every neighbour has an equal vote.
+    Uniform,
+    /// Neighbours vote with the inverse of their distance. If any neighbours are at distance zero,
+    /// only they vote.
+    Distance,
+}
+
+/// Algorithm used to find the nearest neighbours of a record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Use a KD-tree for low dimensional data if the metric supports it, otherwise brute force.
+    Auto,
+    /// Partition records in a KD-tree. Not supported for [`Metric::Cosine`].
+    KdTree,
+    /// Compare every record to all training records.
+    BruteForce,
+}
+
+/// Estimator to train a [`KNeighborsClassifier`]. Defaults to 5 uniformly weighted neighbours by
+/// euclidean distance, found with [`Algorithm::Auto`].
+///
+/// Fitting fails if `k` is zero, the metric is invalid, or a KD-tree is requested for a metric
+/// which does not support it.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::neighbors::{KNeighborsClassifierEstimator, Metric, Weights};
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 0.]), "a"),
+///     (arr1(&[0., 1.]), "a"),
+///     (arr1(&[1., 0.]), "a"),
+///     (arr1(&[5., 5.]), "b"),
+///     (arr1(&[5., 6.]), "b"),
+/// ]);
+///
+/// let model = KNeighborsClassifierEstimator::new(3)
+///     .with_weights(Weights::Distance)
+///     .with_metric(Metric::Manhattan)
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[4., 4.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["b"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct KNeighborsClassifierEstimator {
+    k: usize,
+    weights: Weights,
+    metric: Metric,
+    algorithm: Algorithm,
+    leaf_size: usize,
+}
+
+impl Default for KNeighborsClassifierEstimator {
+    fn default() -> Self {
+        KNeighborsClassifierEstimator::new(5)
+    }
+}
+
+impl KNeighborsClassifierEstimator {
+    /// Create estimator voting over the `k` nearest neighbours.
+    pub fn new(k: usize) -> Self {
+        KNeighborsClassifierEstimator {
+            k,
+            weights: Weights::Uniform,
+            metric: Metric::Euclidean,
+            algorithm: Algorithm::Auto,
+            leaf_size: 30,
+        }
+    }
+
+    /// Set weighting of neighbour votes.
+    pub fn with_weights(self, weights: Weights) -> Self {
+        KNeighborsClassifierEstimator { weights, ..self }
+    }
+
+    /// Set distance metric.
+    pub fn with_metric(self, metric: Metric) -> Self {
+        KNeighborsClassifierEstimator { metric, ..self }
+    }
+
+    /// Set algorithm used to find nearest neighbours.
+    pub fn with_algorithm(self, algorithm: Algorithm) -> Self {
+        KNeighborsClassifierEstimator { algorithm, ..self }
+    }
+
+    /// Set maximum number of records in a leaf of the KD-tree.
+    pub fn with_leaf_size(self, leaf_size: usize) -> Self {
+        KNeighborsClassifierEstimator { leaf_size, ..self }
+    }
+}
+
+/// Represents a fitted k-nearest neighbours classifier. Created with the `fit()` function
+/// implemented for [`KNeighborsClassifierEstimator`].
+#[derive(Debug)]
+pub struct KNeighborsClassifier<Input, Label> {
+    _input: PhantomData<Input>,
+    index: NeighborIndex,
+    targets: Vec<usize>,
+    k: usize,
+    weights: Weights,
+    labels: Vec<Label>,
+}
+
+/// Candidate neighbour, ordered by distance.
+#[derive(Debug, Clone, Copy)]
+struct Neighbor {
+    distance: f64,
+    idx: usize,
+}
+
+impl PartialEq for Neighbor {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Neighbor {}
+
+impl PartialOrd for Neighbor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Neighbor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.idx.cmp(&other.idx))
+    }
+}
+
+/// The `k` nearest candidates seen so far.
+struct NearestNeighbors {
+    k: usize,
+    heap: BinaryHeap<Neighbor>,
+}
+
+impl NearestNeighbors {
+    fn new(k: usize) -> Self {
+        NearestNeighbors {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    fn push(&mut self, neighbor: Neighbor) {
+        if self.heap.len() < self.k {
+            self.heap.push(neighbor);
+        } else if self.heap.peek().is_some_and(|worst| neighbor < *worst) {
+            self.heap.pop();
+            self.heap.push(neighbor);
+        }
+    }
+
+    /// Distance a candidate must beat to be one of the nearest neighbours.
+    fn bound(&self) -> f64 {
+        match self.heap.len() < self.k {
+            true => f64::INFINITY,
+            false => self.heap.peek().map_or(f64::INFINITY, |n| n.distance),
+        }
+    }
+
+    fn into_sorted_vec(self) -> Vec<Neighbor> {
+        self.heap.into_sorted_vec()
+    }
+}
+
+#[derive(Debug)]
+enum KdNode {
+    Leaf {
+        start: usize,
+        end: usize,
+    },
+    Split {
+        dim: usize,
+        value: f64,
+        left: usize,
+        right: usize,
+    },
+}
+
+/// KD-tree over training records. Each split partitions records at the median of the feature
+/// with the largest spread.
+#[derive(Debug)]
+struct KdTree {
+    points: Array2<f64>,
+    metric: Metric,
+    order: Vec<usize>,
+    nodes: Vec<KdNode>,
+}
+
+impl KdTree {
+    fn new(points: Array2<f64>, metric: Metric, leaf_size: usize) -> Self {
+        let mut tree = KdTree {
+            order: (0..points.nrows()).collect(),
+            points,
+            metric,
+            nodes: vec![],
+        };
+
+        tree.build(0, tree.order.len(), leaf_size.max(1));
+
+        tree
+    }
+
+    fn build(&mut self, start: usize, end: usize, leaf_size: usize) -> usize {
+        let node = self.nodes.len();
+
+        if end - start <= leaf_size {
+            self.nodes.push(KdNode::Leaf { start, end });
+            return node;
+        }
+
+        let points = &self.points;
+        let order = &mut self.order[start..end];
+
+        let (dim, spread) = (0..points.ncols())
+            .map(|dim| {
+                let (min, max) = order.iter().fold((f64::MAX, f64::MIN), |(min, max), i| {
+                    let v = points[(*i, dim)];
+                    (min.min(v), max.max(v))
+                });
+
+                (dim, max - min)
+            })
+            .fold((0, f64::NEG_INFINITY), |agg, curr| match curr.1 > agg.1 {
+                true => curr,
+                false => agg,
+            });
+
+        if spread <= 0. {
+            self.nodes.push(KdNode::Leaf { start, end });
+            return node;
+        }
+
+        let mid = order.len() / 2;
+        order.select_nth_unstable_by(mid, |a, b| points[(*a, dim)].total_cmp(&points[(*b, dim)]));
+        let value = points[(order[mid], dim)];
+
+        self.nodes.push(KdNode::Leaf { start, end });
+
+        let left = self.build(start, start + mid, leaf_size);
+        let right = self.build(start + mid, end, leaf_size);
+
+        self.nodes[node] = KdNode::Split {
+            dim,
+            value,
+            left,
+            right,
+        };
+
+        node
+    }
+
+    fn search(&self, node: usize, point: ArrayView1<f64>, nearest: &mut NearestNeighbors) {
+        match self.nodes[node] {
+            KdNode::Leaf { start, end } => {
+                for idx in &self.order[start..end] {
+                    nearest.push(Neighbor {
+                        distance: self.metric.distance(point, self.points.row(*idx)),
+                        idx: *idx,
+                    });
+                }
+            }
+            KdNode::Split {
+                dim,
+                value,
+                left,
+                right,
+            } => {
+                let diff = point[dim] - value;
+                let (near, far) = match diff < 0. {
+                    true => (left, right),
+                    false => (right, left),
+                };
+
+                self.search(near, point, nearest);
+
+                if diff.abs() <= nearest.bound() {
+                    self.search(far, point, nearest);
+                }
+            }
+        }
+    }
+}
+
+/// Index to look up nearest training records.
+#[derive(Debug)]
+enum NeighborIndex {
+    KdTree(KdTree),
+    BruteForce { points: Array2<f64>, metric: Metric },
+}
+
+impl NeighborIndex {
+    /// `k` nearest training records of `point`, from nearest to furthest.
+    fn query(&self, point: ArrayView1<f64>, k: usize) -> Vec<Neighbor> {
+        let mut nearest = NearestNeighbors::new(k);
+
+        match self {
+            NeighborIndex::KdTree(tree) => tree.search(0, point, &mut nearest),
+            NeighborIndex::BruteForce { points, metric } => {
+                for (idx, row) in points.rows().into_iter().enumerate() {
+                    nearest.push(Neighbor {
+                        distance: metric.distance(point, row),
+                        idx,
+                    });
+                }
+            }
+        }
+
+        nearest.into_sorted_vec()
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for KNeighborsClassifierEstimator
+{
+    type Estimator = KNeighborsClassifier<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if self.k == 0 || !self.metric.is_valid() {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+        let features = input.features_array()?;
+
+        let mut targets = vec![0; features.nrows()];
+
+        for (idx, indeces) in input.label_indices(&distinct_labels).iter().enumerate() {
+            for i in indeces {
+                targets[*i] = idx;
+            }
+        }
+
+        let use_kd_tree = match self.algorithm {
+            Algorithm::Auto => self.metric.is_coordinate_bounded() && features.ncols() <= 15,
+            Algorithm::KdTree if self.metric.is_coordinate_bounded() => true,
+            Algorithm::KdTree => return None,
+            Algorithm::BruteForce => false,
+        };
+
+        let index = match use_kd_tree {
+            true => NeighborIndex::KdTree(KdTree::new(features, self.metric, self.leaf_size)),
+            false => NeighborIndex::BruteForce {
+                points: features,
+                metric: self.metric,
+            },
+        };
+
+        Some(KNeighborsClassifier {
+            _input: PhantomData,
+            index,
+            targets,
+            k: self.k,
+            weights: self.weights,
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label>
+    for KNeighborsClassifier<Input, Label>
+{
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let nfeatures = match &self.index {
+            NeighborIndex::KdTree(tree) => tree.points.ncols(),
+            NeighborIndex::BruteForce { points, .. } => points.ncols(),
+        };
+
+        let features =
+            to_feature_matrix(arr.map(|record| record.prepare_for_estimation()), nfeatures)?;
+
+        let mut proba = Array2::zeros((features.nrows(), self.labels.len()));
+
+        for (mut votes, record) in proba.rows_mut().into_iter().zip(features.rows()) {
+            let neighbors = self.index.query(record, self.k);
+            let exact_match = neighbors.iter().any(|n| n.distance == 0.);
+
+            for neighbor in neighbors {
+                let weight = match (self.weights, exact_match) {
+                    (Weights::Uniform, _) => 1.,
+                    (Weights::Distance, true) if neighbor.distance == 0. => 1.,
+                    (Weights::Distance, true) => 0.,
+                    (Weights::Distance, false) => neighbor.distance.recip(),
+                };
+
+                votes[self.targets[neighbor.idx]] += weight;
+            }
+
+            let total = votes.sum();
+            votes /= total;
+        }
+
+        Some(proba)
+    }
+}
+
+/// Estimator to train a [`NearestCentroid`] classifier, which predicts the label with the closest
+/// centroid. Centroids are the mean of the records of each label, or the median per feature for
+/// [`Metric::Manhattan`]. Defaults to euclidean distance without shrinkage.
+///
+/// With a shrink threshold, centroids are shrunk towards the overall centroid, following
+/// Tibshirani et al. (2002). The difference of each feature of a centroid to the overall centroid
+/// is standardized by the pooled within-label standard deviation, reduced in absolute value by the
+/// threshold, and set to zero if it falls below it. Features which do not differ between labels
+/// beyond the threshold then no longer affect predictions.
+///
+/// Probabilities are the softmax of the negative distances to the centroids.
+///
+/// Fitting fails if the metric is invalid, the threshold is negative, or the threshold is set and
+/// there are fewer than two labels, no more records than labels or most features do not vary
+/// within labels.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::neighbors::NearestCentroidEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 0.]), "a"),
+///     (arr1(&[0., 1.]), "a"),
+///     (arr1(&[1., 0.]), "a"),
+///     (arr1(&[5., 5.]), "b"),
+///     (arr1(&[5., 6.]), "b"),
+/// ]);
+///
+/// let model = NearestCentroidEstimator::default()
+///     .with_shrink_threshold(Some(0.5))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[4., 4.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["b"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NearestCentroidEstimator {
+    metric: Metric,
+    shrink_threshold: Option<f64>,
+}
+
+/// Represents a fitted nearest centroid classifier. Created with the `fit()` function implemented
+/// for [`NearestCentroidEstimator`].
+#[derive(Debug)]
+pub struct NearestCentroid<Input, Label> {
+    _input: PhantomData<Input>,
+    centroids: Array2<f64>,
+    metric: Metric,
+    labels: Vec<Label>,
+}
+
+impl Default for NearestCentroidEstimator {
+    fn default() -> Self {
+        NearestCentroidEstimator {
+            metric: Metric::Euclidean,
+            shrink_threshold: None,
+        }
+    }
+}
+
+impl NearestCentroidEstimator {
+    /// Set distance metric between records and centroids.
+    pub fn with_metric(self, metric: Metric) -> Self {
+        NearestCentroidEstimator { metric, ..self }
+    }
+
+    /// Set threshold on standardized centroid differences below which they are shrunk to zero, or
+    /// None to keep centroids as they are.
+    pub fn with_shrink_threshold(self, shrink_threshold: Option<f64>) -> Self {
+        NearestCentroidEstimator {
+            shrink_threshold,
+            ..self
+        }
+    }
+}
+
+/// Median of each column, or None if there are no records.
+fn column_medians(features: &Array2<f64>) -> Option<Array1<f64>> {
+    features
+        .columns()
+        .into_iter()
+        .map(|column| quantile(column.iter().copied(), 0.5))
+        .collect()
+}
+
+/// Shrink centroids towards the overall centroid by `threshold` pooled within-label standard
+/// deviations. Returns None if there are fewer than two labels, no more records than labels or a
+/// feature has no deviation, which requires most features not to vary within labels.
+fn shrink_centroids(
+    centroids: &Array2<f64>,
+    features: &Array2<f64>,
+    label_indices: &[Vec<usize>],
+    threshold: f64,
+) -> Option<Array2<f64>> {
+    let nrecords = features.nrows();
+    let nlabels = label_indices.len();
+
+    // a single label is its own overall centroid, leaving no difference to standardize
+    if nlabels < 2 || nrecords <= nlabels {
+        return None;
+    }
+
+    let overall = features.mean_axis(Axis(0))?;
+
+    let mut within = Array1::<f64>::zeros(features.ncols());
+
+    for (centroid, indeces) in centroids.rows().into_iter().zip(label_indices) {
+        for idx in indeces {
+            within += &(&features.row(*idx) - &centroid).mapv(|v| v.powi(2));
+        }
+    }
+
+    let deviations = (within / (nrecords - nlabels) as f64).sqrt();
+    let deviations = &deviations + quantile(deviations.iter().copied(), 0.5)?;
+
+    if deviations.iter().any(|deviation| *deviation <= 0.) {
+        return None;
+    }
+
+    let mut shrunk = centroids.clone();
+
+    for (mut centroid, indeces) in shrunk.rows_mut().into_iter().zip(label_indices) {
+        let scale = (1. / indeces.len() as f64 - 1. / nrecords as f64).sqrt();
+
+        for ((value, mean), deviation) in centroid.iter_mut().zip(&overall).zip(&deviations) {
+            let spread = scale * deviation;
+            let difference = (*value - mean) / spread;
+            let difference = difference.signum() * (difference.abs() - threshold).max(0.);
+
+            *value = mean + spread * difference;
+        }
+    }
+
+    Some(shrunk)
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for NearestCentroidEstimator
+{
+    type Estimator = NearestCentroid<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if !self.metric.is_valid() || self.shrink_threshold.is_some_and(|t| t < 0.) {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+        let features = input.features_array()?;
+        let label_indices = input.label_indices(&distinct_labels);
+
+        let mut centroids = Array2::zeros((distinct_labels.len(), features.ncols()));
+
+        for (mut centroid, indeces) in centroids.rows_mut().into_iter().zip(&label_indices) {
+            let records = features.select(Axis(0), indeces);
+
+            match self.metric {
+                Metric::Manhattan => centroid.assign(&column_medians(&records)?),
+                _ => centroid.assign(&records.mean_axis(Axis(0))?),
+            }
+        }
+
+        let centroids = match self.shrink_threshold {
+            Some(threshold) => shrink_centroids(&centroids, &features, &label_indices, threshold)?,
+            None => centroids,
+        };
+
+        Some(NearestCentroid {
+            _input: PhantomData,
+            centroids,
+            metric: self.metric,
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<Input, Label> NearestCentroid<Input, Label> {
+    /// Centroid of each label, one row per label.
+    pub fn centroids(&self) -> &Array2<f64> {
+        &self.centroids
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label> for NearestCentroid<Input, Label> {
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.centroids.ncols(),
+        )?;
+
+        let scores = Array2::from_shape_fn(
+            (features.nrows(), self.centroids.nrows()),
+            |(row, label)| {
+                -self
+                    .metric
+                    .distance(features.row(row), self.centroids.row(label))
+            },
+        );
+
+        Some(softmax(scores))
+    }
+}
@@ -0,0 +1,881 @@
+//! Support vector machine classifiers
+
+use std::{collections::VecDeque, marker::PhantomData, rc::Rc};
+
+use ndarray::{concatenate, s, Array1, Array2, ArrayView1, Axis};
+use rand::{rngs::StdRng, seq::SliceRandom};
+
+use crate::{argmax, seeded_rng, Estimatable, Estimator};
+
+use super::{calibration::Platt, to_feature_matrix, ClassificationDataSet, Classifier};
+
+/// Loss on the margin of each record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loss {
+    /// Standard SVM loss `max(0, 1 - y * f(x))`.
+    Hinge,
+    /// Squared hinge loss `max(0, 1 - y * f(x))^2`, which is differentiable.
+    SquaredHinge,
+}
+
+/// Kernel function measuring the similarity of two records. A `gamma` of None is set to
+/// `1 / (nfeatures * variance)` of the training features.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kernel {
+    /// Dot product `<x, x'>`.
+    Linear,
+    /// Radial basis function `exp(-gamma * |x - x'|^2)`.
+    Rbf {
+        /// Inverse width of the kernel, positive.
+        gamma: Option<f64>,
+    },
+    /// Polynomial `(gamma * <x, x'> + coef0)^degree`.
+    Polynomial {
+        /// Degree of the polynomial.
+        degree: u32,
+        /// Scale of the dot product, positive.
+        gamma: Option<f64>,
+        /// Constant term.
+        coef0: f64,
+    },
+    /// Hyperbolic tangent `tanh(gamma * <x, x'> + coef0)`.
+    Sigmoid {
+        /// Scale of the dot product, positive.
+        gamma: Option<f64>,
+        /// Constant term.
+        coef0: f64,
+    },
+}
+
+/// Estimator to train a [`LinearSVC`] classifier with dual coordinate descent. Two labels are
+/// separated with a single hyperplane for the second label, more labels with one hyperplane per
+/// label against all others.
+///
+/// By default, the model is fitted with [`Loss::SquaredHinge`] and `C = 1`, for at most 1000
+/// epochs or until the projected gradient spans less than a tolerance of `1e-4`. The intercept is
+/// learned as the coefficient of a constant feature, so it is regularized like the other
+/// coefficients. Fitting fails if there are fewer than two labels or `C` is not positive.
+///
+/// Probabilities are calibrated with Platt scaling: a sigmoid per hyperplane is fitted on the
+/// decision function values of the training records. With more than two labels, the calibrated
+/// probabilities are normalized to sum to one. Predictions use the decision function directly,
+/// so they may disagree with the most likely label of `predict_proba`.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::svm::{LinearSVCEstimator, Loss};
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 0.]), false),
+///     (arr1(&[1., 0.]), false),
+///     (arr1(&[0., 1.]), false),
+///     (arr1(&[3., 3.]), true),
+///     (arr1(&[4., 3.]), true),
+///     (arr1(&[3., 4.]), true),
+/// ]);
+///
+/// let model = LinearSVCEstimator::new(10.)
+///     .with_loss(Loss::Hinge)
+///     .with_seed(Some(0))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[0.5, 0.5]), arr1(&[3.5, 3.5])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec![false, true]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LinearSVCEstimator {
+    c: f64,
+    loss: Loss,
+    tol: f64,
+    max_iter: usize,
+    fit_intercept: bool,
+    seed: Option<u64>,
+}
+
+/// Represents a fitted linear support vector classifier. Created with the `fit()` function
+/// implemented for [`LinearSVCEstimator`].
+#[derive(Debug)]
+pub struct LinearSVC<Input, Label> {
+    _input: PhantomData<Input>,
+    coefficients: Array2<f64>,
+    intercepts: Array1<f64>,
+    calibration: Vec<Platt>,
+    labels: Vec<Label>,
+}
+
+/// Estimator to train a kernel [`SVC`] with sequential minimal optimization, selecting working
+/// sets with second order information as in libsvm. Two labels are separated with a single
+/// decision function for the second label, more labels with one decision function per label
+/// against all others.
+///
+/// By default, the model is fitted with a [`Kernel::Rbf`] kernel and `C = 1` until the maximal
+/// violating pair is within a tolerance of `1e-3`, for at most 100000 optimization steps per
+/// decision function. Kernel rows are computed on demand and the last 200 rows used are cached.
+/// Fitting fails if there are fewer than two labels, or if `C`, the tolerance, the cache size or
+/// `gamma` are not positive.
+///
+/// Probabilities are calibrated with Platt scaling on the decision function values of the training
+/// records, as for [`LinearSVC`].
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::svm::{Kernel, SVCEstimator};
+///
+/// // points inside a ring belong to a different label than points on the ring
+/// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 0.]), true),
+///     (arr1(&[0.5, 0.]), true),
+///     (arr1(&[0., -0.5]), true),
+///     (arr1(&[3., 0.]), false),
+///     (arr1(&[-3., 0.]), false),
+///     (arr1(&[0., 3.]), false),
+///     (arr1(&[0., -3.]), false),
+/// ]);
+///
+/// let model = SVCEstimator::new(10.)
+///     .with_kernel(Kernel::Rbf { gamma: Some(0.5) })
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[0.2, 0.2]), arr1(&[2.5, 2.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec![true, false]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SVCEstimator {
+    c: f64,
+    kernel: Kernel,
+    tol: f64,
+    max_iter: Option<usize>,
+    cache_size: usize,
+}
+
+/// Represents a fitted kernel support vector classifier, keeping only the support vectors of its
+/// decision functions. Created with the `fit()` function implemented for [`SVCEstimator`].
+#[derive(Debug)]
+pub struct SVC<Input, Label> {
+    _input: PhantomData<Input>,
+    kernel: KernelFunction,
+    support_vectors: Array2<f64>,
+    dual_coefficients: Array2<f64>,
+    intercepts: Array1<f64>,
+    calibration: Vec<Platt>,
+    labels: Vec<Label>,
+}
+
+/// Kernel with a resolved `gamma`.
+#[derive(Debug, Clone, Copy)]
+struct KernelFunction {
+    kernel: Kernel,
+    gamma: f64,
+}
+
+/// Kernel matrix of the training records, computing rows on demand and keeping the most recently
+/// used rows.
+struct KernelCache<'a> {
+    features: &'a Array2<f64>,
+    kernel: KernelFunction,
+    capacity: usize,
+    rows: VecDeque<(usize, Rc<Array1<f64>>)>,
+}
+
+/// Solution of the dual problem of a binary SVC.
+struct DualSolution {
+    alphas: Vec<f64>,
+    rho: f64,
+}
+
+impl Default for LinearSVCEstimator {
+    fn default() -> Self {
+        LinearSVCEstimator::new(1.)
+    }
+}
+
+impl LinearSVCEstimator {
+    /// Create a new linear SVC estimator with inverse regularization strength `c`. Larger values
+    /// penalize margin violations more.
+    pub fn new(c: f64) -> Self {
+        LinearSVCEstimator {
+            c,
+            loss: Loss::SquaredHinge,
+            tol: 1e-4,
+            max_iter: 1000,
+            fit_intercept: true,
+            seed: None,
+        }
+    }
+
+    /// Set loss on the margin.
+    pub fn with_loss(self, loss: Loss) -> Self {
+        LinearSVCEstimator { loss, ..self }
+    }
+
+    /// Set tolerance on the span of the projected gradient for convergence.
+    pub fn with_tol(self, tol: f64) -> Self {
+        LinearSVCEstimator { tol, ..self }
+    }
+
+    /// Set maximum number of epochs over the records.
+    pub fn with_max_iter(self, max_iter: usize) -> Self {
+        LinearSVCEstimator { max_iter, ..self }
+    }
+
+    /// Set whether to fit an intercept per hyperplane.
+    pub fn with_fit_intercept(self, fit_intercept: bool) -> Self {
+        LinearSVCEstimator {
+            fit_intercept,
+            ..self
+        }
+    }
+
+    /// Set seed to shuffle records with, or None to seed from OS entropy.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        LinearSVCEstimator { seed, ..self }
+    }
+
+    /// Solve the dual problem for targets of `1` or `-1`, returning the primal weights.
+    fn dual_coordinate_descent(
+        &self,
+        features: &Array2<f64>,
+        targets: &[f64],
+        rng: &mut StdRng,
+    ) -> Array1<f64> {
+        let (upper, diagonal) = match self.loss {
+            Loss::Hinge => (self.c, 0.),
+            Loss::SquaredHinge => (f64::INFINITY, 1. / (2. * self.c)),
+        };
+
+        let norms: Vec<f64> = features
+            .rows()
+            .into_iter()
+            .map(|row| row.dot(&row) + diagonal)
+            .collect();
+
+        let mut weights = Array1::zeros(features.ncols());
+        let mut alphas = vec![0.; features.nrows()];
+        let mut order: Vec<usize> = (0..features.nrows()).collect();
+
+        for _ in 0..self.max_iter {
+            order.shuffle(rng);
+
+            let mut max_projected = f64::NEG_INFINITY;
+            let mut min_projected = f64::INFINITY;
+
+            for idx in order.iter().copied() {
+                let record = features.row(idx);
+                let gradient = targets[idx] * weights.dot(&record) - 1. + diagonal * alphas[idx];
+
+                let projected = match alphas[idx] {
+                    alpha if alpha <= 0. => gradient.min(0.),
+                    alpha if alpha >= upper => gradient.max(0.),
+                    _ => gradient,
+                };
+
+                max_projected = max_projected.max(projected);
+                min_projected = min_projected.min(projected);
+
+                if projected != 0. && norms[idx] > 0. {
+                    let alpha = (alphas[idx] - gradient / norms[idx]).clamp(0., upper);
+
+                    weights.scaled_add((alpha - alphas[idx]) * targets[idx], &record);
+                    alphas[idx] = alpha;
+                }
+            }
+
+            if max_projected - min_projected < self.tol {
+                break;
+            }
+        }
+
+        weights
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for LinearSVCEstimator
+{
+    type Estimator = LinearSVC<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if self.c <= 0. || self.tol <= 0. {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+
+        if distinct_labels.len() < 2 {
+            return None;
+        }
+
+        let features = input.features_array()?;
+        let nfeatures = features.ncols();
+        let augmented = match self.fit_intercept {
+            true => concatenate![Axis(1), features, Array2::ones((features.nrows(), 1))],
+            false => features.clone(),
+        };
+
+        let classes = input.label_classes(&distinct_labels);
+        let positives: Vec<usize> = match distinct_labels.len() {
+            2 => vec![1],
+            nclasses => (0..nclasses).collect(),
+        };
+
+        let mut rng = seeded_rng(self.seed);
+        let mut coefficients = Array2::zeros((positives.len(), nfeatures));
+        let mut intercepts = Array1::zeros(positives.len());
+        let mut calibration = Vec::with_capacity(positives.len());
+
+        for (output, positive) in positives.iter().enumerate() {
+            let targets: Vec<f64> = classes
+                .iter()
+                .map(|class| match class == positive {
+                    true => 1.,
+                    false => -1.,
+                })
+                .collect();
+
+            let weights = self.dual_coordinate_descent(&augmented, &targets, &mut rng);
+
+            coefficients
+                .row_mut(output)
+                .assign(&weights.slice(s![..nfeatures]));
+
+            if self.fit_intercept {
+                intercepts[output] = weights[nfeatures];
+            }
+
+            let scores = augmented.dot(&weights);
+            let outcomes: Vec<bool> = targets.iter().map(|target| *target > 0.).collect();
+
+            calibration.push(Platt::fit(scores.view(), &outcomes)?);
+        }
+
+        if !coefficients.iter().all(|c| c.is_finite()) {
+            return None;
+        }
+
+        Some(LinearSVC {
+            _input: PhantomData,
+            coefficients,
+            intercepts,
+            calibration,
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<Input, Label> LinearSVC<Input, Label> {
+    /// Coefficients of the model with one column per feature. For two labels, there is a single
+    /// row for the second label. Otherwise there is one row per label, in the same order as the
+    /// labels of the model.
+    pub fn coefficients(&self) -> &Array2<f64> {
+        &self.coefficients
+    }
+
+    /// Intercepts of the model, one per row of coefficients.
+    pub fn intercepts(&self) -> &Array1<f64> {
+        &self.intercepts
+    }
+}
+
+impl<Input: Estimatable, Label> LinearSVC<Input, Label> {
+    /// Signed score of each record per hyperplane, positive on the side of its label. For two
+    /// labels, there is a single column for the second label. Otherwise there is one
+    /// column per label.
+    pub fn decision_function<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.coefficients.ncols(),
+        )?;
+
+        Some(features.dot(&self.coefficients.t()) + &self.intercepts)
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label> for LinearSVC<Input, Label> {
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let scores = self.decision_function(arr)?;
+
+        Some(Platt::one_vs_rest_proba(&self.calibration, &scores))
+    }
+
+    fn predict<I>(&self, arr: I) -> Option<Vec<Label>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        decision_labels(&self.labels, &self.decision_function(arr)?)
+    }
+}
+
+/// Label per row of one-vs-rest decision function values. A single column is the score of the
+/// second of two labels.
+fn decision_labels<Label: Clone>(labels: &[Label], scores: &Array2<f64>) -> Option<Vec<Label>> {
+    scores
+        .rows()
+        .into_iter()
+        .map(|row| match row.len() {
+            1 => labels.get((row[0] > 0.) as usize).cloned(),
+            _ => labels.get(argmax(row.iter().copied())?).cloned(),
+        })
+        .collect()
+}
+
+impl Default for SVCEstimator {
+    fn default() -> Self {
+        SVCEstimator::new(1.)
+    }
+}
+
+impl SVCEstimator {
+    /// Create a new kernel SVC estimator with inverse regularization strength `c`. Larger values
+    /// penalize margin violations more.
+    pub fn new(c: f64) -> Self {
+        SVCEstimator {
+            c,
+            kernel: Kernel::Rbf { gamma: None },
+            tol: 1e-3,
+            max_iter: Some(100_000),
+            cache_size: 200,
+        }
+    }
+
+    /// Set kernel function.
+    pub fn with_kernel(self, kernel: Kernel) -> Self {
+        SVCEstimator { kernel, ..self }
+    }
+
+    /// Set tolerance on the maximal violating pair for convergence.
+    pub fn with_tol(self, tol: f64) -> Self {
+        SVCEstimator { tol, ..self }
+    }
+
+    /// Set maximum number of optimization steps per decision function, or None to run until
+    /// convergence, which may never happen with a very small tolerance.
+    pub fn with_max_iter(self, max_iter: Option<usize>) -> Self {
+        SVCEstimator { max_iter, ..self }
+    }
+
+    /// Set number of kernel matrix rows to keep in the cache.
+    pub fn with_cache_size(self, cache_size: usize) -> Self {
+        SVCEstimator { cache_size, ..self }
+    }
+
+    /// Solve the dual problem for targets of `1` or `-1` with sequential minimal optimization.
+    fn solve(&self, cache: &mut KernelCache, diagonal: &[f64], targets: &[f64]) -> DualSolution {
+        const TAU: f64 = 1e-12;
+
+        let c = self.c;
+        let mut alphas = vec![0.; targets.len()];
+        let mut gradient = vec![-1.; targets.len()];
+        let mut iteration = 0;
+
+        while self.max_iter.is_none_or(|max_iter| iteration < max_iter) {
+            iteration += 1;
+
+            let mut gmax = f64::NEG_INFINITY;
+            let mut first = None;
+
+            for (t, target) in targets.iter().enumerate() {
+                let violation = match *target > 0. {
+                    true if alphas[t] < c => -gradient[t],
+                    false if alphas[t] > 0. => gradient[t],
+                    _ => continue,
+                };
+
+                if violation >= gmax {
+                    gmax = violation;
+                    first = Some(t);
+                }
+            }
+
+            let Some(i) = first else {
+                break;
+            };
+
+            let row_i = cache.row(i);
+
+            let mut gmax2 = f64::NEG_INFINITY;
+            let mut min_objective = f64::INFINITY;
+            let mut second = None;
+
+            for (t, target) in targets.iter().enumerate() {
+                let (difference, violation) = match *target > 0. {
+                    true if alphas[t] > 0. => (gmax + gradient[t], gradient[t]),
+                    false if alphas[t] < c => (gmax - gradient[t], -gradient[t]),
+                    _ => continue,
+                };
+
+                gmax2 = gmax2.max(violation);
+
+                if difference > 0. {
+                    let quad = diagonal[i] + diagonal[t] - 2. * row_i[t];
+                    let objective = -difference.powi(2) / quad.max(TAU);
+
+                    if objective <= min_objective {
+                        min_objective = objective;
+                        second = Some(t);
+                    }
+                }
+            }
+
+            let Some(j) = second.filter(|_| gmax + gmax2 >= self.tol) else {
+                break;
+            };
+
+            let row_j = cache.row(j);
+            let quad = (diagonal[i] + diagonal[j] - 2. * row_i[j]).max(TAU);
+            let (old_i, old_j) = (alphas[i], alphas[j]);
+
+            match targets[i] != targets[j] {
+                true => {
+                    let delta = (-gradient[i] - gradient[j]) / quad;
+                    let diff = old_i - old_j;
+                    alphas[i] += delta;
+                    alphas[j] += delta;
+
+                    if diff > 0. && alphas[j] < 0. {
+                        alphas[j] = 0.;
+                        alphas[i] = diff;
+                    } else if diff <= 0. && alphas[i] < 0. {
+                        alphas[i] = 0.;
+                        alphas[j] = -diff;
+                    }
+
+                    if diff > 0. && alphas[i] > c {
+                        alphas[i] = c;
+                        alphas[j] = c - diff;
+                    } else if diff <= 0. && alphas[j] > c {
+                        alphas[j] = c;
+                        alphas[i] = c + diff;
+                    }
+                }
+                false => {
+                    let delta = (gradient[i] - gradient[j]) / quad;
+                    let sum = old_i + old_j;
+                    alphas[i] -= delta;
+                    alphas[j] += delta;
+
+                    if sum > c && alphas[i] > c {
+                        alphas[i] = c;
+                        alphas[j] = sum - c;
+                    } else if sum <= c && alphas[j] < 0. {
+                        alphas[j] = 0.;
+                        alphas[i] = sum;
+                    }
+
+                    if sum > c && alphas[j] > c {
+                        alphas[j] = c;
+                        alphas[i] = sum - c;
+                    } else if sum <= c && alphas[i] < 0. {
+                        alphas[i] = 0.;
+                        alphas[j] = sum;
+                    }
+                }
+            }
+
+            let delta_i = targets[i] * (alphas[i] - old_i);
+            let delta_j = targets[j] * (alphas[j] - old_j);
+
+            for (t, target) in targets.iter().enumerate() {
+                gradient[t] += target * (row_i[t] * delta_i + row_j[t] * delta_j);
+            }
+        }
+
+        DualSolution {
+            rho: self.rho(&alphas, &gradient, targets),
+            alphas,
+        }
+    }
+
+    /// Offset of the decision function: the average over free support vectors, or the middle of
+    /// the feasible range if there are none.
+    fn rho(&self, alphas: &[f64], gradient: &[f64], targets: &[f64]) -> f64 {
+        let mut upper = f64::INFINITY;
+        let mut lower = f64::NEG_INFINITY;
+        let mut free = 0;
+        let mut free_sum = 0.;
+
+        for ((alpha, gradient), target) in alphas.iter().zip(gradient).zip(targets) {
+            let value = target * gradient;
+
+            match (*alpha >= self.c, *alpha <= 0., *target > 0.) {
+                (true, _, false) | (false, true, true) => upper = upper.min(value),
+                (true, _, true) | (false, true, false) => lower = lower.max(value),
+                _ => {
+                    free += 1;
+                    free_sum += value;
+                }
+            }
+        }
+
+        match free {
+            0 => (upper + lower) / 2.,
+            _ => free_sum / free as f64,
+        }
+    }
+}
+
+impl KernelFunction {
+    /// Resolve the kernel for the training features, or None if `gamma` is not positive.
+    fn new(kernel: Kernel, features: &Array2<f64>) -> Option<KernelFunction> {
+        let gamma = match kernel {
+            Kernel::Linear => 1.,
+            Kernel::Rbf { gamma }
+            | Kernel::Polynomial { gamma, .. }
+            | Kernel::Sigmoid { gamma, .. } => match gamma {
+                Some(gamma) if gamma > 0. => gamma,
+                Some(_) => return None,
+                None => {
+                    let variance = features.var(0.);
+
+                    match variance > 0. {
+                        true => 1. / (features.ncols() as f64 * variance),
+                        false => 1.,
+                    }
+                }
+            },
+        };
+
+        Some(KernelFunction { kernel, gamma })
+    }
+
+    fn evaluate(&self, a: ArrayView1<f64>, b: ArrayView1<f64>) -> f64 {
+        match self.kernel {
+            Kernel::Linear => a.dot(&b),
+            Kernel::Rbf { .. } => {
+                let distance: f64 = a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum();
+
+                (-self.gamma * distance).exp()
+            }
+            Kernel::Polynomial { degree, coef0, .. } => {
+                (self.gamma * a.dot(&b) + coef0).powi(degree as i32)
+            }
+            Kernel::Sigmoid { coef0, .. } => (self.gamma * a.dot(&b) + coef0).tanh(),
+        }
+    }
+
+    /// Kernel values between each row of `a` and each row of `b`.
+    fn matrix(&self, a: &Array2<f64>, b: &Array2<f64>) -> Array2<f64> {
+        Array2::from_shape_fn((a.nrows(), b.nrows()), |(i, j)| {
+            self.evaluate(a.row(i), b.row(j))
+        })
+    }
+}
+
+impl KernelCache<'_> {
+    fn new(features: &Array2<f64>, kernel: KernelFunction, capacity: usize) -> KernelCache<'_> {
+        KernelCache {
+            features,
+            kernel,
+            capacity,
+            rows: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Kernel values between a training record and all training records.
+    fn row(&mut self, idx: usize) -> Rc<Array1<f64>> {
+        let cached = self
+            .rows
+            .iter()
+            .position(|(i, _)| *i == idx)
+            .and_then(|position| self.rows.remove(position));
+
+        let row = match cached {
+            Some((_, row)) => row,
+            None => Rc::new(
+                self.features
+                    .rows()
+                    .into_iter()
+                    .map(|record| self.kernel.evaluate(record, self.features.row(idx)))
+                    .collect(),
+            ),
+        };
+
+        if self.rows.len() >= self.capacity {
+            self.rows.pop_front();
+        }
+
+        self.rows.push_back((idx, row.clone()));
+
+        row
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for SVCEstimator
+{
+    type Estimator = SVC<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if self.c <= 0. || self.tol <= 0. || self.cache_size == 0 {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+
+        if distinct_labels.len() < 2 {
+            return None;
+        }
+
+        let features = input.features_array()?;
+        let kernel = KernelFunction::new(self.kernel, &features)?;
+        let classes = input.label_classes(&distinct_labels);
+        let positives: Vec<usize> = match distinct_labels.len() {
+            2 => vec![1],
+            nclasses => (0..nclasses).collect(),
+        };
+
+        let diagonal: Vec<f64> = features
+            .rows()
+            .into_iter()
+            .map(|record| kernel.evaluate(record, record))
+            .collect();
+
+        let mut cache = KernelCache::new(&features, kernel, self.cache_size);
+
+        let problems: Vec<(Vec<f64>, DualSolution)> = positives
+            .iter()
+            .map(|positive| {
+                let targets: Vec<f64> = classes
+                    .iter()
+                    .map(|class| match class == positive {
+                        true => 1.,
+                        false => -1.,
+                    })
+                    .collect();
+
+                let solution = self.solve(&mut cache, &diagonal, &targets);
+
+                (targets, solution)
+            })
+            .collect();
+
+        let support: Vec<usize> = (0..features.nrows())
+            .filter(|idx| {
+                problems
+                    .iter()
+                    .any(|(_, solution)| solution.alphas[*idx] > 0.)
+            })
+            .collect();
+
+        let support_vectors = features.select(Axis(0), &support);
+        let mut dual_coefficients = Array2::zeros((problems.len(), support.len()));
+
+        for (output, (targets, solution)) in problems.iter().enumerate() {
+            for (column, idx) in support.iter().enumerate() {
+                dual_coefficients[(output, column)] = targets[*idx] * solution.alphas[*idx];
+            }
+        }
+
+        let intercepts: Array1<f64> = problems.iter().map(|(_, solution)| -solution.rho).collect();
+
+        let scores = kernel
+            .matrix(&features, &support_vectors)
+            .dot(&dual_coefficients.t())
+            + &intercepts;
+
+        let calibration = problems
+            .iter()
+            .enumerate()
+            .map(|(output, (targets, _))| {
+                let outcomes: Vec<bool> = targets.iter().map(|target| *target > 0.).collect();
+
+                Platt::fit(scores.column(output), &outcomes)
+            })
+            .collect::<Option<Vec<Platt>>>()?;
+
+        if !scores.iter().all(|score| score.is_finite()) {
+            return None;
+        }
+
+        Some(SVC {
+            _input: PhantomData,
+            kernel,
+            support_vectors,
+            dual_coefficients,
+            intercepts,
+            calibration,
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<Input, Label> SVC<Input, Label> {
+    /// Training records with a non-zero dual coefficient in any decision function.
+    pub fn support_vectors(&self) -> &Array2<f64> {
+        &self.support_vectors
+    }
+
+    /// Label times dual coefficient of each support vector, with one row per decision function
+    /// and one column per support vector.
+    pub fn dual_coefficients(&self) -> &Array2<f64> {
+        &self.dual_coefficients
+    }
+
+    /// Intercepts of the model, one per decision function.
+    pub fn intercepts(&self) -> &Array1<f64> {
+        &self.intercepts
+    }
+}
+
+impl<Input: Estimatable, Label> SVC<Input, Label> {
+    /// Score of each record per decision function, positive on the side of its label. For two
+    /// labels, there is a single column for the second label. Otherwise there is one column per
+    /// label.
+    pub fn decision_function<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.support_vectors.ncols(),
+        )?;
+
+        Some(
+            self.kernel
+                .matrix(&features, &self.support_vectors)
+                .dot(&self.dual_coefficients.t())
+                + &self.intercepts,
+        )
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label> for SVC<Input, Label> {
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let scores = self.decision_function(arr)?;
+
+        Some(Platt::one_vs_rest_proba(&self.calibration, &scores))
+    }
+
+    fn predict<I>(&self, arr: I) -> Option<Vec<Label>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        decision_labels(&self.labels, &self.decision_function(arr)?)
+    }
+}
@@ -0,0 +1,250 @@
+//! Adaptive boosting of weak classifiers.
+
+use ndarray::Array2;
+
+use crate::{
+    classification::{softmax, ClassificationDataSet, Classifier},
+    Estimator, WeightedEstimator,
+};
+
+/// Estimator to train an [`AdaBoostClassifier`] with the SAMME algorithm.
+///
+/// Each boosting round fits the base estimator on reweighted records, and weighs its vote by
+/// `learning_rate * (ln((1 - e) / e) + ln(K - 1))`, with `e` its weighted error rate and `K` the
+/// number of labels. Weights of misclassified records are then multiplied by the exponent of the
+/// vote weight. By default 50 rounds are fitted with a learning rate of 1. Boosting stops early
+/// once a classifier fits the weighted records perfectly, or does no better than guessing.
+///
+/// Probabilities are the softmax of the weighted votes per label, divided by `K - 1`.
+///
+/// Fitting fails if there are fewer than two labels, no rounds, the learning rate is not positive,
+/// or the first classifier does no better than guessing.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::ensemble::adaboost::AdaBoostClassifierEstimator;
+/// use rs_ml::tree::DecisionTreeClassifierEstimator;
+///
+/// // no single threshold separates the labels
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[1.]), "out"),
+///     (arr1(&[2.]), "out"),
+///     (arr1(&[4.]), "in"),
+///     (arr1(&[5.]), "in"),
+///     (arr1(&[7.]), "out"),
+///     (arr1(&[8.]), "out"),
+/// ]);
+///
+/// let stump = DecisionTreeClassifierEstimator::default().with_max_depth(Some(1));
+///
+/// let model = AdaBoostClassifierEstimator::new(stump)
+///     .with_n_estimators(10)
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[1.5]), arr1(&[4.5]), arr1(&[7.5])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["out", "in", "out"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AdaBoostClassifierEstimator<E> {
+    estimator: E,
+    n_estimators: usize,
+    learning_rate: f64,
+}
+
+/// Represents a fitted AdaBoost classifier. Created with the `fit()` function implemented for
+/// [`AdaBoostClassifierEstimator`].
+#[derive(Debug)]
+pub struct AdaBoostClassifier<C, Label> {
+    classifiers: Vec<C>,
+    estimator_weights: Vec<f64>,
+    estimator_errors: Vec<f64>,
+    labels: Vec<Label>,
+}
+
+impl<E> AdaBoostClassifierEstimator<E> {
+    /// Create a new AdaBoost estimator boosting classifiers fitted with `estimator`, fitting 50
+    /// rounds with a learning rate of 1.
+    pub fn new(estimator: E) -> Self {
+        AdaBoostClassifierEstimator {
+            estimator,
+            n_estimators: 50,
+            learning_rate: 1.,
+        }
+    }
+
+    /// Set maximum number of boosting rounds.
+    pub fn with_n_estimators(self, n_estimators: usize) -> Self {
+        AdaBoostClassifierEstimator {
+            n_estimators,
+            ..self
+        }
+    }
+
+    /// Set factor scaling the vote weight of each classifier, trading off against the number of
+    /// rounds.
+    pub fn with_learning_rate(self, learning_rate: f64) -> Self {
+        AdaBoostClassifierEstimator {
+            learning_rate,
+            ..self
+        }
+    }
+}
+
+impl<F, L, E> Estimator<ClassificationDataSet<F, L>> for AdaBoostClassifierEstimator<E>
+where
+    F: Clone,
+    L: PartialEq + Clone,
+    E: WeightedEstimator<ClassificationDataSet<F, L>>,
+    E::Estimator: Classifier<F, L>,
+{
+    type Estimator = AdaBoostClassifier<E::Estimator, L>;
+
+    fn fit(&self, input: &ClassificationDataSet<F, L>) -> Option<Self::Estimator> {
+        let labels = input.distinct_labels();
+        let nlabels = labels.len();
+        let nrecords = input.get_records().len();
+
+        if nlabels < 2 || self.n_estimators == 0 || self.learning_rate <= 0. {
+            return None;
+        }
+
+        let features: Vec<F> = input.get_features().into_iter().cloned().collect();
+        let mut weights = vec![1. / nrecords as f64; nrecords];
+
+        let mut classifiers = vec![];
+        let mut estimator_weights = vec![];
+        let mut estimator_errors = vec![];
+
+        for round in 0..self.n_estimators {
+            let classifier = self.estimator.fit_weighted(input, &weights)?;
+            let predictions = classifier.predict(features.iter().cloned())?;
+
+            let incorrect: Vec<bool> = predictions
+                .iter()
+                .zip(input.get_labels())
+                .map(|(prediction, label)| prediction != label)
+                .collect();
+
+            let error = incorrect
+                .iter()
+                .zip(&weights)
+                .filter_map(|(incorrect, weight)| incorrect.then_some(weight))
+                .sum::<f64>()
+                / weights.iter().sum::<f64>();
+
+            if error <= 0. {
+                classifiers.push(classifier);
+                estimator_weights.push(1.);
+                estimator_errors.push(0.);
+                break;
+            }
+
+            if error >= 1. - 1. / nlabels as f64 {
+                match round {
+                    0 => return None,
+                    _ => break,
+                }
+            }
+
+            let alpha =
+                self.learning_rate * (((1. - error) / error).ln() + (nlabels as f64 - 1.).ln());
+
+            classifiers.push(classifier);
+            estimator_weights.push(alpha);
+            estimator_errors.push(error);
+
+            for (weight, incorrect) in weights.iter_mut().zip(&incorrect) {
+                if *incorrect {
+                    *weight *= alpha.exp();
+                }
+            }
+
+            let total: f64 = weights.iter().sum();
+
+            if !total.is_finite() || total <= 0. {
+                break;
+            }
+
+            weights.iter_mut().for_each(|weight| *weight /= total);
+        }
+
+        Some(AdaBoostClassifier {
+            classifiers,
+            estimator_weights,
+            estimator_errors,
+            labels,
+        })
+    }
+}
+
+impl<C, Label> AdaBoostClassifier<C, Label> {
+    /// Classifier fitted in each boosting round.
+    pub fn classifiers(&self) -> &[C] {
+        &self.classifiers
+    }
+
+    /// Vote weight of each classifier.
+    pub fn estimator_weights(&self) -> &[f64] {
+        &self.estimator_weights
+    }
+
+    /// Weighted error rate of each classifier on the records it was fitted on.
+    pub fn estimator_errors(&self) -> &[f64] {
+        &self.estimator_errors
+    }
+
+    /// Number of boosting rounds fitted, which is lower than requested if boosting stopped early.
+    pub fn n_estimators(&self) -> usize {
+        self.classifiers.len()
+    }
+}
+
+impl<C, L: PartialEq + Clone> AdaBoostClassifier<C, L> {
+    /// Weighted votes per label, normalized by the total vote weight. Rows correspond to each
+    /// record, columns are in the same order as the labels function.
+    pub fn decision_function<F, I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        F: Clone,
+        C: Classifier<F, L>,
+        I: Iterator<Item = F>,
+    {
+        let features: Vec<F> = arr.collect();
+        let mut votes = Array2::zeros((features.len(), self.labels.len()));
+
+        for (classifier, weight) in self.classifiers.iter().zip(&self.estimator_weights) {
+            let predictions = classifier.predict(features.iter().cloned())?;
+
+            for (row, prediction) in predictions.iter().enumerate() {
+                let idx = self.labels.iter().position(|label| label == prediction)?;
+                votes[(row, idx)] += weight;
+            }
+        }
+
+        Some(votes / self.estimator_weights.iter().sum::<f64>())
+    }
+}
+
+impl<F, L, C> Classifier<F, L> for AdaBoostClassifier<C, L>
+where
+    F: Clone,
+    L: PartialEq + Clone,
+    C: Classifier<F, L>,
+{
+    fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = F>,
+    {
+        let votes = self.decision_function(arr)?;
+
+        Some(softmax(votes / (self.labels.len() as f64 - 1.)))
+    }
+}
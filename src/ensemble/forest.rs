@@ -0,0 +1,273 @@
+//! Random forests of decision trees.
+
+use std::marker::PhantomData;
+
+use ndarray::{Array1, Array2};
+use rand::Rng;
+
+use crate::{
+    argmax,
+    classification::{to_feature_matrix, ClassificationDataSet, Classifier},
+    seeded_rng,
+    tree::{Criterion, DecisionTreeClassifierEstimator, MaxFeatures, Tree},
+    Estimatable, Estimator,
+};
+
+/// Estimator to train a [`RandomForestClassifier`].
+///
+/// Each tree is grown on a bootstrap sample of the records, drawn with replacement, and considers
+/// a random subset of the features at each split. By default 100 trees are grown until their
+/// leaves are pure, considering the square root of the number of features at each split.
+/// Fitting fails if there are no trees, if the tree hyperparameters are invalid, or if an
+/// out-of-bag score is requested without bootstrapping.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::ensemble::forest::RandomForestClassifierEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[1., 0.]), "low"),
+///     (arr1(&[2., 1.]), "low"),
+///     (arr1(&[3., 0.]), "low"),
+///     (arr1(&[7., 1.]), "high"),
+///     (arr1(&[8., 0.]), "high"),
+///     (arr1(&[9., 1.]), "high"),
+/// ]);
+///
+/// let model = RandomForestClassifierEstimator::new(25)
+///     .with_seed(Some(42))
+///     .with_oob_score(true)
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[8.5, 1.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["high"]);
+/// assert!(model.oob_score().is_some());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RandomForestClassifierEstimator {
+    n_estimators: usize,
+    tree: DecisionTreeClassifierEstimator,
+    bootstrap: bool,
+    oob_score: bool,
+    seed: Option<u64>,
+}
+
+/// Represents a fitted random forest classifier. Created with the `fit()` function implemented
+/// for [`RandomForestClassifierEstimator`].
+#[derive(Debug)]
+pub struct RandomForestClassifier<Input, Label> {
+    _input: PhantomData<Input>,
+    trees: Vec<Tree>,
+    oob_score: Option<f64>,
+    labels: Vec<Label>,
+}
+
+impl Default for RandomForestClassifierEstimator {
+    fn default() -> Self {
+        RandomForestClassifierEstimator::new(100)
+    }
+}
+
+impl RandomForestClassifierEstimator {
+    /// Create a new random forest estimator growing `n_estimators` trees.
+    pub fn new(n_estimators: usize) -> Self {
+        RandomForestClassifierEstimator {
+            n_estimators,
+            tree: DecisionTreeClassifierEstimator::default().with_max_features(MaxFeatures::Sqrt),
+            bootstrap: true,
+            oob_score: false,
+            seed: None,
+        }
+    }
+
+    /// Set impurity criterion of the trees.
+    pub fn with_criterion(self, criterion: Criterion) -> Self {
+        RandomForestClassifierEstimator {
+            tree: self.tree.with_criterion(criterion),
+            ..self
+        }
+    }
+
+    /// Set maximum depth of the trees, or None to split until leaves are pure.
+    pub fn with_max_depth(self, max_depth: Option<usize>) -> Self {
+        RandomForestClassifierEstimator {
+            tree: self.tree.with_max_depth(max_depth),
+            ..self
+        }
+    }
+
+    /// Set minimum number of records required to split a node.
+    pub fn with_min_samples_split(self, min_samples_split: usize) -> Self {
+        RandomForestClassifierEstimator {
+            tree: self.tree.with_min_samples_split(min_samples_split),
+            ..self
+        }
+    }
+
+    /// Set minimum number of records in each leaf.
+    pub fn with_min_samples_leaf(self, min_samples_leaf: usize) -> Self {
+        RandomForestClassifierEstimator {
+            tree: self.tree.with_min_samples_leaf(min_samples_leaf),
+            ..self
+        }
+    }
+
+    /// Set number of features considered at each split.
+    pub fn with_max_features(self, max_features: MaxFeatures) -> Self {
+        RandomForestClassifierEstimator {
+            tree: self.tree.with_max_features(max_features),
+            ..self
+        }
+    }
+
+    /// Set whether each tree is grown on a bootstrap sample, or on all records.
+    pub fn with_bootstrap(self, bootstrap: bool) -> Self {
+        RandomForestClassifierEstimator { bootstrap, ..self }
+    }
+
+    /// Set whether to estimate accuracy on the records left out of each bootstrap sample.
+    pub fn with_oob_score(self, oob_score: bool) -> Self {
+        RandomForestClassifierEstimator { oob_score, ..self }
+    }
+
+    /// Set seed to draw bootstrap samples and split candidates with, or None to seed from OS
+    /// entropy.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        RandomForestClassifierEstimator { seed, ..self }
+    }
+}
+
+impl<Input, Label> RandomForestClassifier<Input, Label> {
+    /// Impurity based importance of each feature, averaged over the trees.
+    pub fn feature_importances(&self) -> Array1<f64> {
+        self.trees
+            .iter()
+            .map(|tree| tree.feature_importances())
+            .reduce(|agg, curr| agg + curr)
+            .map(|total| total / self.trees.len() as f64)
+            .unwrap_or_default()
+    }
+
+    /// Accuracy of predicting each training record with only the trees it was left out of the
+    /// bootstrap sample for. None if not requested, or if every record was drawn for every tree.
+    pub fn oob_score(&self) -> Option<f64> {
+        self.oob_score
+    }
+
+    /// Number of trees in the forest.
+    pub fn n_estimators(&self) -> usize {
+        self.trees.len()
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for RandomForestClassifierEstimator
+{
+    type Estimator = RandomForestClassifier<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if self.n_estimators == 0 || (self.oob_score && !self.bootstrap) {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+        let features = input.features_array()?;
+        let classes = input.label_classes(&distinct_labels);
+
+        let nrecords = features.nrows();
+        let mut rng = seeded_rng(self.seed);
+        let mut trees = Vec::with_capacity(self.n_estimators);
+        let mut oob_proba = Array2::zeros((nrecords, distinct_labels.len()));
+
+        for _ in 0..self.n_estimators {
+            let mut weights = vec![0.; nrecords];
+
+            match self.bootstrap {
+                true => (0..nrecords).for_each(|_| weights[rng.random_range(0..nrecords)] += 1.),
+                false => weights.fill(1.),
+            };
+
+            let tree = self.tree.build(
+                &features,
+                &classes,
+                distinct_labels.len(),
+                &weights,
+                &mut rng,
+            )?;
+
+            if self.oob_score {
+                for (idx, _) in weights.iter().enumerate().filter(|(_, w)| **w == 0.) {
+                    let mut row = oob_proba.row_mut(idx);
+                    row += tree.value(features.row(idx));
+                }
+            }
+
+            trees.push(tree);
+        }
+
+        let oob_score = match self.oob_score {
+            true => oob_accuracy(&oob_proba, &classes),
+            false => None,
+        };
+
+        Some(RandomForestClassifier {
+            _input: PhantomData,
+            trees,
+            oob_score,
+            labels: distinct_labels,
+        })
+    }
+}
+
+/// Accuracy of the summed out-of-bag probabilities, over the records with any out-of-bag tree.
+fn oob_accuracy(oob_proba: &Array2<f64>, classes: &[usize]) -> Option<f64> {
+    let (correct, total) = oob_proba
+        .rows()
+        .into_iter()
+        .zip(classes)
+        .filter(|(row, _)| row.sum() > 0.)
+        .fold((0, 0), |(correct, total), (row, class)| {
+            match argmax(row.iter().copied()) == Some(*class) {
+                true => (correct + 1, total + 1),
+                false => (correct, total + 1),
+            }
+        });
+
+    match total {
+        0 => None,
+        _ => Some(correct as f64 / total as f64),
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label>
+    for RandomForestClassifier<Input, Label>
+{
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.trees.first()?.nfeatures(),
+        )?;
+
+        let mut proba = Array2::zeros((features.nrows(), self.labels.len()));
+
+        for (mut row, record) in proba.rows_mut().into_iter().zip(features.rows()) {
+            for tree in &self.trees {
+                row += tree.value(record);
+            }
+        }
+
+        Some(proba / self.trees.len() as f64)
+    }
+}
@@ -0,0 +1,429 @@
+//! Gradient boosted decision trees.
+
+use std::marker::PhantomData;
+
+use ndarray::{concatenate, Array1, Array2, Axis};
+use rand::seq::index::sample;
+
+use crate::{
+    classification::{
+        log_softmax, stratified_split, to_feature_matrix, ClassificationDataSet, Classifier,
+    },
+    seeded_rng,
+    tree::{SplitCriterion, Targets, Tree, TreeParams},
+    Estimatable, Estimator,
+};
+
+/// Estimator to train a [`GradientBoostingClassifier`].
+///
+/// Each boosting stage fits a regression tree per class to the negative gradient of the log loss,
+/// with a Newton step as leaf value. Binary problems use a single tree per stage. By default 100
+/// stages of depth 3 trees are fitted with a learning rate of 0.1 on all records.
+///
+/// If `n_iter_no_change` is set, a stratified `validation_fraction` of the records is held out,
+/// and boosting stops once the validation loss has not improved by at least `tol` for
+/// `n_iter_no_change` stages.
+///
+/// Fitting fails if there are fewer than two labels, if the learning rate is not positive, if the
+/// subsample or validation fractions are not between zero and one, or if early stopping holds out
+/// no records.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::ensemble::gradient_boosting::GradientBoostingClassifierEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[1., 0.]), "low"),
+///     (arr1(&[2., 1.]), "low"),
+///     (arr1(&[3., 0.]), "low"),
+///     (arr1(&[7., 1.]), "high"),
+///     (arr1(&[8., 0.]), "high"),
+///     (arr1(&[9., 1.]), "high"),
+/// ]);
+///
+/// let model = GradientBoostingClassifierEstimator::new(20)
+///     .with_learning_rate(0.3)
+///     .with_max_depth(Some(1))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[8.5, 1.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["high"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GradientBoostingClassifierEstimator {
+    n_estimators: usize,
+    learning_rate: f64,
+    subsample: f64,
+    max_depth: Option<usize>,
+    min_samples_leaf: usize,
+    validation_fraction: f64,
+    n_iter_no_change: Option<usize>,
+    tol: f64,
+    seed: Option<u64>,
+}
+
+/// Represents a fitted gradient boosting classifier. Created with the `fit()` function implemented
+/// for [`GradientBoostingClassifierEstimator`].
+#[derive(Debug)]
+pub struct GradientBoostingClassifier<Input, Label> {
+    _input: PhantomData<Input>,
+    init: Array1<f64>,
+    stages: Vec<Vec<Tree>>,
+    learning_rate: f64,
+    nfeatures: usize,
+    labels: Vec<Label>,
+}
+
+impl Default for GradientBoostingClassifierEstimator {
+    fn default() -> Self {
+        GradientBoostingClassifierEstimator::new(100)
+    }
+}
+
+impl GradientBoostingClassifierEstimator {
+    /// Create a new gradient boosting estimator fitting at most `n_estimators` stages.
+    pub fn new(n_estimators: usize) -> Self {
+        GradientBoostingClassifierEstimator {
+            n_estimators,
+            learning_rate: 0.1,
+            subsample: 1.,
+            max_depth: Some(3),
+            min_samples_leaf: 1,
+            validation_fraction: 0.1,
+            n_iter_no_change: None,
+            tol: 1e-4,
+            seed: None,
+        }
+    }
+
+    /// Set factor the contribution of each tree is shrunk by.
+    pub fn with_learning_rate(self, learning_rate: f64) -> Self {
+        GradientBoostingClassifierEstimator {
+            learning_rate,
+            ..self
+        }
+    }
+
+    /// Set fraction of the training records drawn without replacement to fit each stage on.
+    pub fn with_subsample(self, subsample: f64) -> Self {
+        GradientBoostingClassifierEstimator { subsample, ..self }
+    }
+
+    /// Set maximum depth of the trees, or None to split until leaves are pure.
+    pub fn with_max_depth(self, max_depth: Option<usize>) -> Self {
+        GradientBoostingClassifierEstimator { max_depth, ..self }
+    }
+
+    /// Set minimum number of records in each leaf.
+    pub fn with_min_samples_leaf(self, min_samples_leaf: usize) -> Self {
+        GradientBoostingClassifierEstimator {
+            min_samples_leaf,
+            ..self
+        }
+    }
+
+    /// Set fraction of records held out to decide on early stopping.
+    pub fn with_validation_fraction(self, validation_fraction: f64) -> Self {
+        GradientBoostingClassifierEstimator {
+            validation_fraction,
+            ..self
+        }
+    }
+
+    /// Set number of stages without improvement of the validation loss to stop after, or None to
+    /// fit all stages on all records.
+    pub fn with_n_iter_no_change(self, n_iter_no_change: Option<usize>) -> Self {
+        GradientBoostingClassifierEstimator {
+            n_iter_no_change,
+            ..self
+        }
+    }
+
+    /// Set minimum decrease of the validation loss to count as an improvement.
+    pub fn with_tol(self, tol: f64) -> Self {
+        GradientBoostingClassifierEstimator { tol, ..self }
+    }
+
+    /// Set seed to draw subsamples and the validation split with, or None to seed from OS
+    /// entropy.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        GradientBoostingClassifierEstimator { seed, ..self }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.learning_rate > 0.
+            && self.subsample > 0.
+            && self.subsample <= 1.
+            && self.validation_fraction > 0.
+            && self.validation_fraction < 1.
+            && self.n_iter_no_change != Some(0)
+            && self.tol >= 0.
+    }
+}
+
+impl<Input, Label> GradientBoostingClassifier<Input, Label> {
+    /// Number of boosting stages fitted, which can be less than requested with early stopping.
+    pub fn n_estimators(&self) -> usize {
+        self.stages.len()
+    }
+
+    fn raw_scores(&self, features: &Array2<f64>) -> Array2<f64> {
+        let mut raw = Array2::zeros((features.nrows(), self.init.len()));
+        raw += &self.init;
+
+        for stage in &self.stages {
+            add_stage(&mut raw, stage, features, self.learning_rate);
+        }
+
+        raw
+    }
+}
+
+impl<Input: Estimatable, Label> GradientBoostingClassifier<Input, Label> {
+    /// Raw additive score of each record. For two labels, there is a single column with the log
+    /// odds of the second label. Otherwise there is one column per label.
+    pub fn decision_function<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.nfeatures,
+        )?;
+
+        Some(self.raw_scores(&features))
+    }
+}
+
+/// Add the scaled predictions of each tree in a stage to its column of raw scores.
+fn add_stage(raw: &mut Array2<f64>, stage: &[Tree], features: &Array2<f64>, learning_rate: f64) {
+    for (mut row, record) in raw.rows_mut().into_iter().zip(features.rows()) {
+        for (score, tree) in row.iter_mut().zip(stage) {
+            *score += learning_rate * tree.value(record)[0];
+        }
+    }
+}
+
+/// Log probability of each label from raw scores, where a single column holds binary log odds.
+fn log_proba(raw: &Array2<f64>) -> Array2<f64> {
+    match raw.ncols() {
+        1 => log_softmax(concatenate![Axis(1), Array2::zeros((raw.nrows(), 1)), *raw]),
+        _ => log_softmax(raw.clone()),
+    }
+}
+
+/// Mean negative log likelihood of the true classes.
+fn log_loss(raw: &Array2<f64>, classes: &[usize]) -> f64 {
+    let log_proba = log_proba(raw);
+
+    -classes
+        .iter()
+        .enumerate()
+        .map(|(idx, class)| log_proba[(idx, *class)])
+        .sum::<f64>()
+        / classes.len() as f64
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for GradientBoostingClassifierEstimator
+{
+    type Estimator = GradientBoostingClassifier<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if !self.is_valid() {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+        let nclasses = distinct_labels.len();
+
+        if nclasses < 2 {
+            return None;
+        }
+
+        let features = input.features_array()?;
+        let classes = input.label_classes(&distinct_labels);
+        let mut rng = seeded_rng(self.seed);
+
+        let (train, validation) = match self.n_iter_no_change {
+            Some(_) => stratified_split(
+                &input.label_indices(&distinct_labels),
+                self.validation_fraction,
+                &mut rng,
+            ),
+            None => ((0..features.nrows()).collect(), vec![]),
+        };
+
+        if self.n_iter_no_change.is_some() && validation.is_empty() {
+            return None;
+        }
+
+        let train_features = features.select(Axis(0), &train);
+        let train_classes: Vec<usize> = train.iter().map(|idx| classes[*idx]).collect();
+        let validation_features = features.select(Axis(0), &validation);
+        let validation_classes: Vec<usize> = validation.iter().map(|idx| classes[*idx]).collect();
+
+        let ntrain = train.len();
+        let noutputs = match nclasses {
+            2 => 1,
+            _ => nclasses,
+        };
+
+        let mut priors = Array1::zeros(nclasses);
+        train_classes
+            .iter()
+            .for_each(|class| priors[*class] += 1. / ntrain as f64);
+        let priors = priors.mapv(|p: f64| p.clamp(f64::EPSILON, 1. - f64::EPSILON));
+
+        let init = match noutputs {
+            1 => Array1::from_elem(1, (priors[1] / priors[0]).ln()),
+            _ => priors.ln(),
+        };
+
+        let params = TreeParams {
+            criterion: SplitCriterion::SquaredError,
+            max_depth: self.max_depth,
+            min_samples_split: 2,
+            min_samples_leaf: self.min_samples_leaf,
+            max_features: features.ncols(),
+        };
+
+        let mut raw = Array2::zeros((ntrain, noutputs));
+        raw += &init;
+        let mut validation_raw = Array2::zeros((validation.len(), noutputs));
+        validation_raw += &init;
+
+        let nsubsample = ((self.subsample * ntrain as f64) as usize).max(1);
+        let mut stages = vec![];
+        let mut best_loss = f64::INFINITY;
+        let mut no_change = 0;
+
+        for _ in 0..self.n_estimators {
+            let mut weights = vec![0.; ntrain];
+
+            match nsubsample < ntrain {
+                true => sample(&mut rng, ntrain, nsubsample)
+                    .into_iter()
+                    .for_each(|idx| weights[idx] = 1.),
+                false => weights.fill(1.),
+            };
+
+            let proba = log_proba(&raw).exp();
+            let mut stage = Vec::with_capacity(noutputs);
+
+            for output in 0..noutputs {
+                let class = match noutputs {
+                    1 => 1,
+                    _ => output,
+                };
+
+                let residuals: Vec<f64> = train_classes
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, c)| (*c == class) as usize as f64 - proba[(idx, class)])
+                    .collect();
+
+                let mut tree = params.build(
+                    &train_features,
+                    &Targets::Values(&residuals),
+                    &weights,
+                    &mut rng,
+                )?;
+
+                newton_step(&mut tree, &train_features, &residuals, &weights, nclasses);
+                stage.push(tree);
+            }
+
+            add_stage(&mut raw, &stage, &train_features, self.learning_rate);
+            add_stage(
+                &mut validation_raw,
+                &stage,
+                &validation_features,
+                self.learning_rate,
+            );
+            stages.push(stage);
+
+            if let Some(n_iter_no_change) = self.n_iter_no_change {
+                let loss = log_loss(&validation_raw, &validation_classes);
+
+                match loss < best_loss - self.tol {
+                    true => no_change = 0,
+                    false => no_change += 1,
+                };
+
+                best_loss = best_loss.min(loss);
+
+                if no_change >= n_iter_no_change {
+                    break;
+                }
+            }
+        }
+
+        if !raw.iter().all(|v| v.is_finite()) {
+            return None;
+        }
+
+        Some(GradientBoostingClassifier {
+            _input: PhantomData,
+            init,
+            stages,
+            learning_rate: self.learning_rate,
+            nfeatures: features.ncols(),
+            labels: distinct_labels,
+        })
+    }
+}
+
+/// Replace the leaf values of a tree fitted to log loss residuals by a Newton step.
+fn newton_step(
+    tree: &mut Tree,
+    features: &Array2<f64>,
+    residuals: &[f64],
+    weights: &[f64],
+    nclasses: usize,
+) {
+    let mut numerators = vec![0.; tree.nodes.len()];
+    let mut denominators = vec![0.; tree.nodes.len()];
+
+    for (idx, record) in features.rows().into_iter().enumerate() {
+        let leaf = tree.leaf(record);
+        let residual = residuals[idx];
+
+        numerators[leaf] += weights[idx] * residual;
+        denominators[leaf] += weights[idx] * residual.abs() * (1. - residual.abs());
+    }
+
+    let scale = match nclasses {
+        2 => 1.,
+        _ => (nclasses - 1) as f64 / nclasses as f64,
+    };
+
+    for (node, (numerator, denominator)) in numerators.iter().zip(denominators).enumerate() {
+        if tree.nodes[node].is_leaf() {
+            tree.nodes[node].value[0] = match denominator > 1e-150 {
+                true => scale * numerator / denominator,
+                false => 0.,
+            };
+        }
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label>
+    for GradientBoostingClassifier<Input, Label>
+{
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        Some(log_proba(&self.decision_function(arr)?).exp())
+    }
+}
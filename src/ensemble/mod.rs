@@ -0,0 +1,101 @@
+//! Ensemble models combining many base models into one.
+
+use std::fmt::Debug;
+
+use ndarray::Array2;
+
+use crate::{
+    classification::{ClassificationDataSet, Classifier},
+    Estimator,
+};
+
+pub mod adaboost;
+pub mod forest;
+pub mod gradient_boosting;
+pub mod stacking;
+pub mod voting;
+
+/// Object safe counterpart of [`Classifier`], so fitted classifiers of different types can be
+/// combined in one ensemble. Implemented for every [`Classifier`].
+pub trait DynClassifier<Features, Label>: Debug {
+    /// Labels on which the model is fitted.
+    fn labels(&self) -> &[Label];
+
+    /// Estimates likelihood of each label per record, see [`Classifier::predict_proba`].
+    fn predict_proba(&self, features: &[Features]) -> Option<Array2<f64>>;
+
+    /// Most likely label per record, see [`Classifier::predict`].
+    fn predict(&self, features: &[Features]) -> Option<Vec<Label>>;
+}
+
+/// Object safe counterpart of [`Estimator`] for estimators fitting a [`Classifier`], so estimators
+/// of different types can be combined in one ensemble. Implemented for every such [`Estimator`].
+pub trait DynClassifierEstimator<Features, Label>: Debug {
+    /// Fit a classifier on the dataset.
+    fn fit(
+        &self,
+        input: &ClassificationDataSet<Features, Label>,
+    ) -> Option<Box<dyn DynClassifier<Features, Label>>>;
+}
+
+impl<Features, Label, C> DynClassifier<Features, Label> for C
+where
+    Features: Clone,
+    Label: Clone,
+    C: Classifier<Features, Label> + Debug,
+{
+    fn labels(&self) -> &[Label] {
+        Classifier::labels(self)
+    }
+
+    fn predict_proba(&self, features: &[Features]) -> Option<Array2<f64>> {
+        Classifier::predict_proba(self, features.iter().cloned())
+    }
+
+    fn predict(&self, features: &[Features]) -> Option<Vec<Label>> {
+        Classifier::predict(self, features.iter().cloned())
+    }
+}
+
+impl<Features, Label, E> DynClassifierEstimator<Features, Label> for E
+where
+    Features: Clone,
+    Label: Clone,
+    E: Estimator<ClassificationDataSet<Features, Label>> + Debug,
+    E::Estimator: Classifier<Features, Label> + Debug + 'static,
+{
+    fn fit(
+        &self,
+        input: &ClassificationDataSet<Features, Label>,
+    ) -> Option<Box<dyn DynClassifier<Features, Label>>> {
+        Some(Box::new(Estimator::fit(self, input)?))
+    }
+}
+
+/// Index into `labels` of each label of a member classifier. Returns None if the member has a
+/// label not among `labels`.
+fn label_columns<Label: PartialEq>(member: &[Label], labels: &[Label]) -> Option<Vec<usize>> {
+    member
+        .iter()
+        .map(|label| labels.iter().position(|l| l == label))
+        .collect()
+}
+
+/// Probabilities of a member classifier with columns reordered to `labels`. Labels the member was
+/// not fitted on have zero probability. Returns None if the member has a label not among `labels`.
+fn aligned_proba<Features, Label: PartialEq>(
+    member: &dyn DynClassifier<Features, Label>,
+    labels: &[Label],
+    features: &[Features],
+) -> Option<Array2<f64>> {
+    let columns = label_columns(member.labels(), labels)?;
+    let proba = member.predict_proba(features)?;
+
+    let mut aligned = Array2::zeros((features.len(), labels.len()));
+
+    for (column, idx) in proba.columns().into_iter().zip(columns) {
+        aligned.column_mut(idx).assign(&column);
+    }
+
+    Some(aligned)
+}
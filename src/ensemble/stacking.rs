@@ -0,0 +1,244 @@
+//! Stacking classifier learning to combine classifiers of any type.
+
+use ndarray::{concatenate, Array1, Array2, Axis};
+
+use crate::{
+    classification::{fold_complement, stratified_folds, ClassificationDataSet, Classifier},
+    seeded_rng, Estimator,
+};
+
+use super::{aligned_proba, DynClassifier, DynClassifierEstimator};
+
+/// Estimator to train a [`StackingClassifier`], which fits a final classifier on the
+/// probabilities predicted by its members.
+///
+/// Records are split into `cv` stratified folds, 5 by default. The features of the final
+/// classifier are the probabilities of each member, fitted on the other folds, for the records of
+/// each fold, so the final classifier learns how members behave on records they have not seen.
+/// Members are then refitted on all records. Probabilities of members are aligned to the labels
+/// of the dataset in order of first appearance, concatenated in order of addition.
+///
+/// `fit` returns None if there are no members, `cv` is less than 2, any label has fewer than `cv`
+/// records, or a member or the final estimator fails to fit.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::logistic::LogisticRegressionEstimator;
+/// use rs_ml::classification::naive_bayes::GaussianNBEstimator;
+/// use rs_ml::ensemble::stacking::StackingClassifierEstimator;
+/// use rs_ml::tree::DecisionTreeClassifierEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(
+///     (0..30).map(|idx| match idx % 3 {
+///         0 => (arr1(&[idx as f64 / 30., 0.]), "origin"),
+///         1 => (arr1(&[5., idx as f64 / 30.]), "east"),
+///         _ => (arr1(&[idx as f64 / 30., 5.]), "north"),
+///     }),
+/// );
+///
+/// let model = StackingClassifierEstimator::new(LogisticRegressionEstimator::default())
+///     .with_estimator(GaussianNBEstimator::default())
+///     .with_estimator(DecisionTreeClassifierEstimator::default().with_seed(Some(0)))
+///     .with_cv(3)
+///     .with_seed(Some(0))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[5., 1.]), arr1(&[1., 5.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["east", "north"]);
+/// ```
+#[derive(Debug)]
+pub struct StackingClassifierEstimator<Features, Label, E> {
+    estimators: Vec<Box<dyn DynClassifierEstimator<Features, Label>>>,
+    final_estimator: E,
+    cv: usize,
+    seed: Option<u64>,
+}
+
+/// Represents a fitted stacking classifier. Created with the `fit()` function implemented for
+/// [`StackingClassifierEstimator`].
+#[derive(Debug)]
+pub struct StackingClassifier<Features, Label, C> {
+    classifiers: Vec<Box<dyn DynClassifier<Features, Label>>>,
+    final_classifier: C,
+    labels: Vec<Label>,
+}
+
+impl<Features, Label, E> StackingClassifierEstimator<Features, Label, E> {
+    /// Create a new stacking estimator without members, fitting the final classifier with
+    /// `final_estimator` on 5 folds.
+    pub fn new(final_estimator: E) -> Self {
+        StackingClassifierEstimator {
+            estimators: vec![],
+            final_estimator,
+            cv: 5,
+            seed: None,
+        }
+    }
+
+    /// Add a member estimator.
+    pub fn with_estimator<M>(mut self, estimator: M) -> Self
+    where
+        M: DynClassifierEstimator<Features, Label> + 'static,
+    {
+        self.estimators.push(Box::new(estimator));
+        self
+    }
+
+    /// Set number of cross-validation folds, at least 2.
+    pub fn with_cv(self, cv: usize) -> Self {
+        StackingClassifierEstimator { cv, ..self }
+    }
+
+    /// Set seed to assign records to folds with, or None to seed from OS entropy.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        StackingClassifierEstimator { seed, ..self }
+    }
+}
+
+/// Probabilities of all members aligned to `labels`, concatenated per record.
+fn stacked_proba<Features, Label: PartialEq>(
+    classifiers: &[Box<dyn DynClassifier<Features, Label>>],
+    labels: &[Label],
+    features: &[Features],
+) -> Option<Array2<f64>> {
+    let probas = classifiers
+        .iter()
+        .map(|classifier| aligned_proba(classifier.as_ref(), labels, features))
+        .collect::<Option<Vec<_>>>()?;
+
+    let views: Vec<_> = probas.iter().map(|proba| proba.view()).collect();
+
+    concatenate(Axis(1), &views).ok()
+}
+
+impl<Features, Label, E> Estimator<ClassificationDataSet<Features, Label>>
+    for StackingClassifierEstimator<Features, Label, E>
+where
+    Features: Clone,
+    Label: PartialEq + Clone,
+    E: Estimator<ClassificationDataSet<Array1<f64>, Label>>,
+    E::Estimator: Classifier<Array1<f64>, Label>,
+{
+    type Estimator = StackingClassifier<Features, Label, E::Estimator>;
+
+    fn fit(&self, input: &ClassificationDataSet<Features, Label>) -> Option<Self::Estimator> {
+        let labels = input.distinct_labels();
+        let label_indices = input.label_indices(&labels);
+
+        if self.estimators.is_empty()
+            || self.cv < 2
+            || label_indices.iter().any(|indeces| indeces.len() < self.cv)
+        {
+            return None;
+        }
+
+        let nlabels = labels.len();
+        let folds = stratified_folds(&label_indices, self.cv, &mut seeded_rng(self.seed));
+        let mut meta_features =
+            Array2::zeros((input.dataset.len(), self.estimators.len() * nlabels));
+
+        for (fold, held_out) in folds.iter().enumerate() {
+            let train = input.select(&fold_complement(&folds, fold), Label::clone);
+            let features: Vec<Features> = held_out
+                .iter()
+                .map(|idx| input.dataset[*idx].features.clone())
+                .collect();
+
+            let classifiers = self
+                .estimators
+                .iter()
+                .map(|estimator| estimator.fit(&train))
+                .collect::<Option<Vec<_>>>()?;
+
+            let proba = stacked_proba(&classifiers, &labels, &features)?;
+
+            for (row, idx) in proba.rows().into_iter().zip(held_out) {
+                meta_features.row_mut(*idx).assign(&row);
+            }
+        }
+
+        let meta_dataset = ClassificationDataSet::from(
+            meta_features
+                .rows()
+                .into_iter()
+                .zip(input.get_labels())
+                .map(|(row, label)| (row.to_owned(), label.clone())),
+        );
+
+        let final_classifier = self.final_estimator.fit(&meta_dataset)?;
+
+        let classifiers = self
+            .estimators
+            .iter()
+            .map(|estimator| estimator.fit(input))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(StackingClassifier {
+            classifiers,
+            final_classifier,
+            labels,
+        })
+    }
+}
+
+impl<Features, Label, C> StackingClassifier<Features, Label, C> {
+    /// Members fitted on all records, in order of addition.
+    pub fn classifiers(&self) -> &[Box<dyn DynClassifier<Features, Label>>] {
+        &self.classifiers
+    }
+
+    /// Classifier combining the probabilities of the members.
+    pub fn final_classifier(&self) -> &C {
+        &self.final_classifier
+    }
+}
+
+impl<Features, Label, C> StackingClassifier<Features, Label, C>
+where
+    Label: PartialEq,
+{
+    /// Features of the final classifier: probabilities of each member, one row per record.
+    pub fn transform<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Features>,
+    {
+        let features: Vec<Features> = arr.collect();
+
+        stacked_proba(&self.classifiers, &self.labels, &features)
+    }
+}
+
+impl<Features, Label, C> Classifier<Features, Label> for StackingClassifier<Features, Label, C>
+where
+    Label: PartialEq + Clone,
+    C: Classifier<Array1<f64>, Label>,
+{
+    fn labels(&self) -> &[Label] {
+        self.final_classifier.labels()
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Features>,
+    {
+        let meta_features = self.transform(arr)?;
+
+        self.final_classifier
+            .predict_proba(meta_features.rows().into_iter().map(|row| row.to_owned()))
+    }
+
+    fn predict<I>(&self, arr: I) -> Option<Vec<Label>>
+    where
+        I: Iterator<Item = Features>,
+    {
+        let meta_features = self.transform(arr)?;
+
+        self.final_classifier
+            .predict(meta_features.rows().into_iter().map(|row| row.to_owned()))
+    }
+}
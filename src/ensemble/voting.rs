@@ -0,0 +1,200 @@
+//! Voting classifier combining classifiers of any type.
+
+use ndarray::Array2;
+
+use crate::{
+    classification::{ClassificationDataSet, Classifier},
+    Estimator,
+};
+
+use super::{aligned_proba, label_columns, DynClassifier, DynClassifierEstimator};
+
+/// How members of a [`VotingClassifier`] are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Voting {
+    /// Each member votes for the label it predicts. Probabilities are the weighted fraction of
+    /// votes per label.
+    Hard,
+    /// Probabilities are the weighted average of the probabilities of the members.
+    Soft,
+}
+
+/// Estimator to train a [`VotingClassifier`] on members fitted on the same dataset.
+///
+/// Members are added with `with_estimator` and weighted equally unless weights are set. Fitted
+/// members may order their labels differently, probabilities and votes are aligned to the labels
+/// of the dataset in order of first appearance.
+///
+/// `fit` returns None if there are no members, the number of weights differs from the number of
+/// members, any weight is negative or all weights are zero, or a member fails to fit.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::classification::logistic::LogisticRegressionEstimator;
+/// use rs_ml::classification::naive_bayes::GaussianNBEstimator;
+/// use rs_ml::ensemble::voting::{Voting, VotingClassifierEstimator};
+/// use rs_ml::tree::DecisionTreeClassifierEstimator;
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 0.]), "origin"),
+///     (arr1(&[0.5, 0.]), "origin"),
+///     (arr1(&[5., 0.]), "east"),
+///     (arr1(&[5.5, 0.5]), "east"),
+///     (arr1(&[0., 5.]), "north"),
+///     (arr1(&[0.5, 5.5]), "north"),
+/// ]);
+///
+/// let model = VotingClassifierEstimator::new()
+///     .with_estimator(GaussianNBEstimator::default())
+///     .with_estimator(LogisticRegressionEstimator::default())
+///     .with_estimator(DecisionTreeClassifierEstimator::default().with_seed(Some(0)))
+///     .with_weights(Some(vec![1., 2., 1.]))
+///     .with_voting(Voting::Soft)
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[5., 1.]), arr1(&[1., 5.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["east", "north"]);
+/// ```
+#[derive(Debug)]
+pub struct VotingClassifierEstimator<Features, Label> {
+    estimators: Vec<Box<dyn DynClassifierEstimator<Features, Label>>>,
+    weights: Option<Vec<f64>>,
+    voting: Voting,
+}
+
+/// Represents a fitted voting classifier. Created with the `fit()` function implemented for
+/// [`VotingClassifierEstimator`].
+#[derive(Debug)]
+pub struct VotingClassifier<Features, Label> {
+    classifiers: Vec<Box<dyn DynClassifier<Features, Label>>>,
+    weights: Vec<f64>,
+    voting: Voting,
+    labels: Vec<Label>,
+}
+
+impl<Features, Label> Default for VotingClassifierEstimator<Features, Label> {
+    fn default() -> Self {
+        VotingClassifierEstimator::new()
+    }
+}
+
+impl<Features, Label> VotingClassifierEstimator<Features, Label> {
+    /// Create a new hard voting estimator without members.
+    pub fn new() -> Self {
+        VotingClassifierEstimator {
+            estimators: vec![],
+            weights: None,
+            voting: Voting::Hard,
+        }
+    }
+
+    /// Add a member estimator.
+    pub fn with_estimator<E>(mut self, estimator: E) -> Self
+    where
+        E: DynClassifierEstimator<Features, Label> + 'static,
+    {
+        self.estimators.push(Box::new(estimator));
+        self
+    }
+
+    /// Set weight of each member, in order of addition, or None to weight members equally.
+    pub fn with_weights(self, weights: Option<Vec<f64>>) -> Self {
+        VotingClassifierEstimator { weights, ..self }
+    }
+
+    /// Set how members are combined.
+    pub fn with_voting(self, voting: Voting) -> Self {
+        VotingClassifierEstimator { voting, ..self }
+    }
+}
+
+impl<Features, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Features, Label>>
+    for VotingClassifierEstimator<Features, Label>
+{
+    type Estimator = VotingClassifier<Features, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Features, Label>) -> Option<Self::Estimator> {
+        let weights = match &self.weights {
+            Some(weights) => weights.clone(),
+            None => vec![1.; self.estimators.len()],
+        };
+
+        if self.estimators.is_empty()
+            || weights.len() != self.estimators.len()
+            || weights.iter().any(|weight| *weight < 0.)
+            || weights.iter().sum::<f64>() <= 0.
+        {
+            return None;
+        }
+
+        let labels = input.distinct_labels();
+
+        let classifiers = self
+            .estimators
+            .iter()
+            .map(|estimator| {
+                let classifier = estimator.fit(input)?;
+                label_columns(classifier.labels(), &labels)?;
+
+                Some(classifier)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(VotingClassifier {
+            classifiers,
+            weights,
+            voting: self.voting,
+            labels,
+        })
+    }
+}
+
+impl<Features, Label> VotingClassifier<Features, Label> {
+    /// Fitted members, in order of addition.
+    pub fn classifiers(&self) -> &[Box<dyn DynClassifier<Features, Label>>] {
+        &self.classifiers
+    }
+
+    /// Weight of each member.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+}
+
+impl<Features, Label: PartialEq + Clone> Classifier<Features, Label>
+    for VotingClassifier<Features, Label>
+{
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Features>,
+    {
+        let features: Vec<Features> = arr.collect();
+        let mut proba = Array2::zeros((features.len(), self.labels.len()));
+
+        for (classifier, weight) in self.classifiers.iter().zip(&self.weights) {
+            match self.voting {
+                Voting::Soft => {
+                    proba +=
+                        &(aligned_proba(classifier.as_ref(), &self.labels, &features)? * *weight);
+                }
+                Voting::Hard => {
+                    for (row, prediction) in classifier.predict(&features)?.iter().enumerate() {
+                        let idx = self.labels.iter().position(|label| label == prediction)?;
+                        proba[(row, idx)] += weight;
+                    }
+                }
+            }
+        }
+
+        Some(proba / self.weights.iter().sum::<f64>())
+    }
+}
@@ -24,12 +24,18 @@ use classification::ClassificationDataSet;
 use ndarray::Array1;
 use ndarray::Axis;
 use num_traits::Float;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 pub mod classification;
 pub mod dimensionality_reduction;
+pub mod ensemble;
 pub mod metrics;
+pub mod neural_network;
 pub mod regression;
+pub mod sampling;
 pub mod transformer;
+pub mod tree;
 
 /// Trait for fitting classification and regression models, and transformers.
 ///
@@ -72,6 +78,15 @@ pub trait Estimator<Input> {
     fn fit(&self, input: &Input) -> Option<Self::Estimator>;
 }
 
+/// Trait for estimators which can weigh the records they are fitted on, such as base models of
+/// boosting ensembles.
+pub trait WeightedEstimator<Input>: Estimator<Input> {
+    /// Fit model based on given inputs with a non-negative weight per record, or None if the
+    /// estimator was not able to fit to the input data or the weights do not match the records.
+    /// Fitting with all weights equal to one is equivalent to `fit()`.
+    fn fit_weighted(&self, input: &Input, sample_weights: &[f64]) -> Option<Self::Estimator>;
+}
+
 /// Trait to prepare a struct for training or inference
 pub trait Estimatable {
     /// Prepare for an estimator to train or make inference based on this data
@@ -137,6 +152,14 @@ pub fn train_test_split<Feature, Label>(
     )
 }
 
+/// Random number generator seeded with `seed`, or from OS entropy if no seed is given.
+fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    }
+}
+
 /// Mean of elements in an iterator.
 fn iterative_mean<I, F, R>(it: I) -> Option<R>
 where
@@ -158,6 +181,36 @@ where
     })
 }
 
+/// Index of the first largest value, or None if there are no values.
+fn argmax<I: Iterator<Item = f64>>(values: I) -> Option<usize> {
+    let mut values = values.enumerate().peekable();
+    values.peek()?;
+
+    let (idx, _) = values.fold((0, f64::NEG_INFINITY), |agg, curr| match agg.1 < curr.1 {
+        true => curr,
+        false => agg,
+    });
+
+    Some(idx)
+}
+
+/// Quantile `q` of values, interpolated linearly between the closest ranks. Returns None if there
+/// are no values or `q` is not between 0 and 1.
+fn quantile<I: IntoIterator<Item = f64>>(values: I, q: f64) -> Option<f64> {
+    if !(0. ..=1.).contains(&q) {
+        return None;
+    }
+
+    let mut sorted: Vec<f64> = values.into_iter().collect();
+    sorted.sort_by(f64::total_cmp);
+
+    let position = q * (sorted.len().checked_sub(1)? as f64);
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+
+    Some(sorted[lower] + (position - lower as f64) * (sorted[upper] - sorted[lower]))
+}
+
 #[cfg(test)]
 mod tests {
     use ndarray::{arr1, arr2, Array1};
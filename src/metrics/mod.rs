@@ -2,6 +2,7 @@
 
 use std::ops::{Div, Mul};
 
+use ndarray::Array2;
 use num_traits::Float;
 
 use crate::iterative_mean;
@@ -52,3 +53,37 @@ where
 
     iterative_mean(pairs)
 }
+
+/// Fraction of wrongly predicted labels in multi-label indicator matrices, with a row per record
+/// and a column per label. Returns None if the shapes differ or there are no labels.
+pub fn hamming_loss(ground_truth: &Array2<bool>, inference: &Array2<bool>) -> Option<f64> {
+    if ground_truth.dim() != inference.dim() || ground_truth.is_empty() {
+        return None;
+    }
+
+    let wrong = ground_truth
+        .iter()
+        .zip(inference)
+        .filter(|(gt, inference)| gt != inference)
+        .count() as f64;
+
+    Some(wrong / ground_truth.len() as f64)
+}
+
+/// Fraction of records whose labels are all predicted correctly in multi-label indicator
+/// matrices, with a row per record and a column per label. Returns None if the shapes differ or
+/// there are no records.
+pub fn subset_accuracy(ground_truth: &Array2<bool>, inference: &Array2<bool>) -> Option<f64> {
+    if ground_truth.dim() != inference.dim() || ground_truth.nrows() == 0 {
+        return None;
+    }
+
+    let correct = ground_truth
+        .rows()
+        .into_iter()
+        .zip(inference.rows())
+        .filter(|(gt, inference)| gt == inference)
+        .count() as f64;
+
+    Some(correct / ground_truth.nrows() as f64)
+}
@@ -0,0 +1,617 @@
+//! Neural network models.
+
+use std::marker::PhantomData;
+
+use ndarray::{Array, Array1, Array2, Axis, Dimension, Zip};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use crate::{
+    argmax,
+    classification::{
+        log_softmax, softmax, stratified_split, to_feature_matrix, ClassificationDataSet,
+        Classifier,
+    },
+    seeded_rng, Estimatable, Estimator,
+};
+
+/// Activation function applied after each hidden layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activation {
+    /// Rectified linear unit `max(0, x)`.
+    Relu,
+    /// Hyperbolic tangent.
+    Tanh,
+    /// Logistic sigmoid `1 / (1 + exp(-x))`.
+    Logistic,
+}
+
+/// Optimization algorithm used to fit the weights of a network.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Optimizer {
+    /// Stochastic gradient descent with momentum.
+    Sgd {
+        /// Step size.
+        learning_rate: f64,
+        /// Fraction of the previous update added to each update, between 0 and 1.
+        momentum: f64,
+    },
+    /// Adam, which scales steps by running estimates of the first and second moments of the
+    /// gradients.
+    Adam {
+        /// Step size.
+        learning_rate: f64,
+        /// Decay of the first moment estimate, between 0 and 1.
+        beta_1: f64,
+        /// Decay of the second moment estimate, between 0 and 1.
+        beta_2: f64,
+        /// Term added to the denominator for numerical stability.
+        epsilon: f64,
+    },
+}
+
+/// Estimator to train a [`MLPClassifier`], a fully connected feed forward network with a softmax
+/// output layer minimizing cross entropy.
+///
+/// By default, the network has a single hidden layer of 100 [`Activation::Relu`] units and is
+/// fitted with [`Optimizer::Adam`] at a learning rate of `1e-3` on batches of at most 200
+/// records, with an L2 penalty of `1e-4`, for at most 200 epochs. Training stops once the
+/// training loss has not improved by `1e-4` for 10 epochs. With early stopping, a stratified
+/// fraction of the records is held out instead, training stops once the validation accuracy has
+/// not improved for 10 epochs, and the weights with the best validation accuracy are kept.
+///
+/// Weights are initialized with Glorot uniform initialization. Features are not scaled, so
+/// standardizing them first usually helps convergence. Fitting fails if there are fewer than two
+/// labels, if a hyperparameter is invalid, or if training diverges.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::neural_network::{Activation, MLPClassifierEstimator};
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 0.]), false),
+///     (arr1(&[1., 1.]), false),
+///     (arr1(&[0., 1.]), true),
+///     (arr1(&[1., 0.]), true),
+/// ]);
+///
+/// let model = MLPClassifierEstimator::new(vec![8])
+///     .with_activation(Activation::Tanh)
+///     .with_max_iter(2000)
+///     .with_seed(Some(1))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict(dataset.get_features().into_iter().cloned()).unwrap();
+///
+/// assert_eq!(predictions, vec![false, false, true, true]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MLPClassifierEstimator {
+    hidden_layer_sizes: Vec<usize>,
+    activation: Activation,
+    optimizer: Optimizer,
+    alpha: f64,
+    batch_size: Option<usize>,
+    max_iter: usize,
+    tol: f64,
+    n_iter_no_change: usize,
+    early_stopping: bool,
+    validation_fraction: f64,
+    seed: Option<u64>,
+}
+
+/// Represents a fitted multi-layer perceptron classifier. Created with the `fit()` function
+/// implemented for [`MLPClassifierEstimator`].
+#[derive(Debug)]
+pub struct MLPClassifier<Input, Label> {
+    _input: PhantomData<Input>,
+    params: Params,
+    activation: Activation,
+    loss_curve: Vec<f64>,
+    labels: Vec<Label>,
+}
+
+/// Weights and biases of each layer, or gradients and optimizer state with the same shapes.
+#[derive(Debug, Clone)]
+struct Params {
+    weights: Vec<Array2<f64>>,
+    biases: Vec<Array1<f64>>,
+}
+
+/// Running state of an optimizer.
+struct OptimizerState {
+    first: Params,
+    second: Params,
+    step: i32,
+}
+
+impl Default for MLPClassifierEstimator {
+    fn default() -> Self {
+        MLPClassifierEstimator::new(vec![100])
+    }
+}
+
+impl MLPClassifierEstimator {
+    /// Create a new estimator with the given number of units per hidden layer.
+    pub fn new(hidden_layer_sizes: Vec<usize>) -> Self {
+        MLPClassifierEstimator {
+            hidden_layer_sizes,
+            activation: Activation::Relu,
+            optimizer: Optimizer::Adam {
+                learning_rate: 1e-3,
+                beta_1: 0.9,
+                beta_2: 0.999,
+                epsilon: 1e-8,
+            },
+            alpha: 1e-4,
+            batch_size: None,
+            max_iter: 200,
+            tol: 1e-4,
+            n_iter_no_change: 10,
+            early_stopping: false,
+            validation_fraction: 0.1,
+            seed: None,
+        }
+    }
+
+    /// Set activation function of the hidden layers.
+    pub fn with_activation(self, activation: Activation) -> Self {
+        MLPClassifierEstimator { activation, ..self }
+    }
+
+    /// Set optimization algorithm.
+    pub fn with_optimizer(self, optimizer: Optimizer) -> Self {
+        MLPClassifierEstimator { optimizer, ..self }
+    }
+
+    /// Set strength of the L2 penalty on the weights.
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        MLPClassifierEstimator { alpha, ..self }
+    }
+
+    /// Set number of records per gradient step, or None for at most 200 records.
+    pub fn with_batch_size(self, batch_size: Option<usize>) -> Self {
+        MLPClassifierEstimator { batch_size, ..self }
+    }
+
+    /// Set maximum number of epochs.
+    pub fn with_max_iter(self, max_iter: usize) -> Self {
+        MLPClassifierEstimator { max_iter, ..self }
+    }
+
+    /// Set minimum improvement of the loss, or of the validation accuracy with early stopping.
+    pub fn with_tol(self, tol: f64) -> Self {
+        MLPClassifierEstimator { tol, ..self }
+    }
+
+    /// Set number of epochs without improvement to stop after.
+    pub fn with_n_iter_no_change(self, n_iter_no_change: usize) -> Self {
+        MLPClassifierEstimator {
+            n_iter_no_change,
+            ..self
+        }
+    }
+
+    /// Set whether to stop on the accuracy of held out records rather than the training loss.
+    pub fn with_early_stopping(self, early_stopping: bool) -> Self {
+        MLPClassifierEstimator {
+            early_stopping,
+            ..self
+        }
+    }
+
+    /// Set fraction of records held out for early stopping.
+    pub fn with_validation_fraction(self, validation_fraction: f64) -> Self {
+        MLPClassifierEstimator {
+            validation_fraction,
+            ..self
+        }
+    }
+
+    /// Set seed to initialize weights and shuffle records with, or None to seed from OS entropy.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        MLPClassifierEstimator { seed, ..self }
+    }
+
+    fn is_valid(&self) -> bool {
+        let optimizer_valid = match self.optimizer {
+            Optimizer::Sgd {
+                learning_rate,
+                momentum,
+            } => learning_rate > 0. && (0. ..=1.).contains(&momentum),
+            Optimizer::Adam {
+                learning_rate,
+                beta_1,
+                beta_2,
+                epsilon,
+            } => {
+                learning_rate > 0.
+                    && (0. ..1.).contains(&beta_1)
+                    && (0. ..1.).contains(&beta_2)
+                    && epsilon > 0.
+            }
+        };
+
+        optimizer_valid
+            && self.hidden_layer_sizes.iter().all(|size| *size > 0)
+            && self.alpha >= 0.
+            && self.batch_size != Some(0)
+            && self.tol >= 0.
+            && self.n_iter_no_change > 0
+            && (!self.early_stopping
+                || (self.validation_fraction > 0. && self.validation_fraction < 1.))
+    }
+
+    /// Glorot uniform initialization of the weights, with zero biases.
+    fn initialize(&self, nfeatures: usize, nclasses: usize, rng: &mut StdRng) -> Params {
+        let sizes: Vec<usize> = [nfeatures]
+            .into_iter()
+            .chain(self.hidden_layer_sizes.iter().copied())
+            .chain([nclasses])
+            .collect();
+
+        let gain = match self.activation {
+            Activation::Logistic => 2.,
+            _ => 6.,
+        };
+
+        let weights = sizes
+            .windows(2)
+            .map(|pair| {
+                let bound = (gain / (pair[0] + pair[1]) as f64).sqrt();
+
+                Array2::from_shape_fn((pair[0], pair[1]), |_| rng.random_range(-bound..bound))
+            })
+            .collect();
+
+        let biases = sizes[1..].iter().map(|size| Array1::zeros(*size)).collect();
+
+        Params { weights, biases }
+    }
+}
+
+impl Activation {
+    fn apply(&self, mut values: Array2<f64>) -> Array2<f64> {
+        match self {
+            Activation::Relu => values.mapv_inplace(|v| v.max(0.)),
+            Activation::Tanh => values.mapv_inplace(f64::tanh),
+            Activation::Logistic => values.mapv_inplace(|v| 1. / (1. + (-v).exp())),
+        };
+
+        values
+    }
+
+    /// Multiply the gradient with respect to the activations by the derivative of the
+    /// activation, expressed in the activations themselves.
+    fn backpropagate(&self, gradient: &mut Array2<f64>, activations: &Array2<f64>) {
+        gradient.zip_mut_with(activations, |g, a| {
+            *g *= match self {
+                Activation::Relu => (*a > 0.) as usize as f64,
+                Activation::Tanh => 1. - a * a,
+                Activation::Logistic => a * (1. - a),
+            }
+        });
+    }
+}
+
+impl Params {
+    fn zeros_like(other: &Params) -> Params {
+        Params {
+            weights: other
+                .weights
+                .iter()
+                .map(|w| Array2::zeros(w.dim()))
+                .collect(),
+            biases: other
+                .biases
+                .iter()
+                .map(|b| Array1::zeros(b.dim()))
+                .collect(),
+        }
+    }
+
+    /// Activations of each layer, starting with the input and ending with the output scores.
+    fn forward(&self, features: Array2<f64>, activation: Activation) -> Vec<Array2<f64>> {
+        let mut activations = vec![features];
+        let nlayers = self.weights.len();
+
+        for (layer, (weights, biases)) in self.weights.iter().zip(&self.biases).enumerate() {
+            let values = activations[layer].dot(weights) + biases;
+
+            activations.push(match layer + 1 < nlayers {
+                true => activation.apply(values),
+                false => values,
+            });
+        }
+
+        activations
+    }
+
+    /// Mean cross entropy with L2 penalty of a batch, and its gradients.
+    fn loss_gradient(
+        &self,
+        features: Array2<f64>,
+        targets: &Array2<f64>,
+        activation: Activation,
+        alpha: f64,
+    ) -> (f64, Params) {
+        let nrecords = features.nrows() as f64;
+        let mut activations = self.forward(features, activation);
+
+        let log_proba = log_softmax(activations.pop().unwrap_or_default());
+        let penalty: f64 = self.weights.iter().map(|w| w.pow2().sum()).sum();
+        let loss = -(&log_proba * targets).sum() / nrecords + alpha * penalty / (2. * nrecords);
+
+        let mut delta = (log_proba.exp() - targets) / nrecords;
+        let mut gradients = Params::zeros_like(self);
+
+        for layer in (0..self.weights.len()).rev() {
+            gradients.weights[layer] =
+                activations[layer].t().dot(&delta) + &self.weights[layer] * (alpha / nrecords);
+            gradients.biases[layer] = delta.sum_axis(Axis(0));
+
+            if layer > 0 {
+                delta = delta.dot(&self.weights[layer].t());
+                activation.backpropagate(&mut delta, &activations[layer]);
+            }
+        }
+
+        (loss, gradients)
+    }
+
+    fn predict_proba(&self, features: Array2<f64>, activation: Activation) -> Array2<f64> {
+        softmax(self.forward(features, activation).pop().unwrap_or_default())
+    }
+
+    fn is_finite(&self) -> bool {
+        self.weights.iter().all(|w| w.iter().all(|v| v.is_finite()))
+            && self.biases.iter().all(|b| b.iter().all(|v| v.is_finite()))
+    }
+}
+
+impl OptimizerState {
+    fn new(params: &Params) -> OptimizerState {
+        OptimizerState {
+            first: Params::zeros_like(params),
+            second: Params::zeros_like(params),
+            step: 0,
+        }
+    }
+
+    /// Update parameters in place from their gradients.
+    fn update(&mut self, optimizer: &Optimizer, params: &mut Params, gradients: &Params) {
+        self.step += 1;
+
+        for layer in 0..params.weights.len() {
+            optimizer.step(
+                self.step,
+                &mut params.weights[layer],
+                &gradients.weights[layer],
+                &mut self.first.weights[layer],
+                &mut self.second.weights[layer],
+            );
+            optimizer.step(
+                self.step,
+                &mut params.biases[layer],
+                &gradients.biases[layer],
+                &mut self.first.biases[layer],
+                &mut self.second.biases[layer],
+            );
+        }
+    }
+}
+
+impl Optimizer {
+    /// Update one array of parameters, with `first` and `second` holding the momentum or moment
+    /// estimates of the parameters.
+    fn step<D: Dimension>(
+        &self,
+        step: i32,
+        param: &mut Array<f64, D>,
+        gradient: &Array<f64, D>,
+        first: &mut Array<f64, D>,
+        second: &mut Array<f64, D>,
+    ) {
+        match *self {
+            Optimizer::Sgd {
+                learning_rate,
+                momentum,
+            } => Zip::from(param)
+                .and(gradient)
+                .and(first)
+                .for_each(|p, g, velocity| {
+                    *velocity = momentum * *velocity - learning_rate * g;
+                    *p += *velocity;
+                }),
+            Optimizer::Adam {
+                learning_rate,
+                beta_1,
+                beta_2,
+                epsilon,
+            } => {
+                let step_size =
+                    learning_rate * (1. - beta_2.powi(step)).sqrt() / (1. - beta_1.powi(step));
+
+                Zip::from(param)
+                    .and(gradient)
+                    .and(first)
+                    .and(second)
+                    .for_each(|p, g, m, v| {
+                        *m = beta_1 * *m + (1. - beta_1) * g;
+                        *v = beta_2 * *v + (1. - beta_2) * g * g;
+                        *p -= step_size * *m / (v.sqrt() + epsilon);
+                    })
+            }
+        }
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for MLPClassifierEstimator
+{
+    type Estimator = MLPClassifier<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        if !self.is_valid() {
+            return None;
+        }
+
+        let distinct_labels = input.distinct_labels();
+        let nclasses = distinct_labels.len();
+
+        if nclasses < 2 {
+            return None;
+        }
+
+        let features = input.features_array()?;
+        let classes = input.label_classes(&distinct_labels);
+        let mut rng = seeded_rng(self.seed);
+
+        let (train, validation) = match self.early_stopping {
+            true => stratified_split(
+                &input.label_indices(&distinct_labels),
+                self.validation_fraction,
+                &mut rng,
+            ),
+            false => ((0..features.nrows()).collect(), vec![]),
+        };
+
+        if self.early_stopping && validation.is_empty() {
+            return None;
+        }
+
+        let mut targets = Array2::zeros((features.nrows(), nclasses));
+        classes
+            .iter()
+            .enumerate()
+            .for_each(|(idx, class)| targets[(idx, *class)] = 1.);
+
+        let validation_features = features.select(Axis(0), &validation);
+        let batch_size = self.batch_size.unwrap_or(200).min(train.len());
+
+        let mut params = self.initialize(features.ncols(), nclasses, &mut rng);
+        let mut state = OptimizerState::new(&params);
+        let mut order = train.clone();
+        let mut loss_curve = vec![];
+        let mut best_loss = f64::INFINITY;
+        let mut best_score = f64::NEG_INFINITY;
+        let mut best_params = params.clone();
+        let mut no_change = 0;
+
+        for _ in 0..self.max_iter {
+            order.shuffle(&mut rng);
+
+            let mut epoch_loss = 0.;
+
+            for batch in order.chunks(batch_size) {
+                let (loss, gradients) = params.loss_gradient(
+                    features.select(Axis(0), batch),
+                    &targets.select(Axis(0), batch),
+                    self.activation,
+                    self.alpha,
+                );
+
+                state.update(&self.optimizer, &mut params, &gradients);
+                epoch_loss += loss * batch.len() as f64;
+            }
+
+            let epoch_loss = epoch_loss / train.len() as f64;
+
+            if !epoch_loss.is_finite() || !params.is_finite() {
+                return None;
+            }
+
+            loss_curve.push(epoch_loss);
+
+            let improved = match self.early_stopping {
+                true => {
+                    let proba = params.predict_proba(validation_features.clone(), self.activation);
+                    let score = validation
+                        .iter()
+                        .zip(proba.rows())
+                        .filter(|(idx, row)| argmax(row.iter().copied()) == Some(classes[**idx]))
+                        .count() as f64
+                        / validation.len() as f64;
+
+                    let improved = score > best_score + self.tol;
+
+                    if score > best_score {
+                        best_score = score;
+                        best_params = params.clone();
+                    }
+
+                    improved
+                }
+                false => {
+                    let improved = epoch_loss < best_loss - self.tol;
+                    best_loss = best_loss.min(epoch_loss);
+
+                    improved
+                }
+            };
+
+            no_change = match improved {
+                true => 0,
+                false => no_change + 1,
+            };
+
+            if no_change >= self.n_iter_no_change {
+                break;
+            }
+        }
+
+        Some(MLPClassifier {
+            _input: PhantomData,
+            params: match self.early_stopping {
+                true => best_params,
+                false => params,
+            },
+            activation: self.activation,
+            loss_curve,
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<Input, Label> MLPClassifier<Input, Label> {
+    /// Mean training loss of each epoch.
+    pub fn loss_curve(&self) -> &[f64] {
+        &self.loss_curve
+    }
+
+    /// Number of epochs trained.
+    pub fn n_iter(&self) -> usize {
+        self.loss_curve.len()
+    }
+
+    /// Weight matrix of each layer, with one row per input and one column per unit.
+    pub fn coefficients(&self) -> &[Array2<f64>] {
+        &self.params.weights
+    }
+
+    /// Bias vector of each layer.
+    pub fn intercepts(&self) -> &[Array1<f64>] {
+        &self.params.biases
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label> for MLPClassifier<Input, Label> {
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.params.weights.first()?.nrows(),
+        )?;
+
+        Some(self.params.predict_proba(features, self.activation))
+    }
+}
@@ -0,0 +1,106 @@
+//! Baseline regressors ignoring the features of records.
+
+use ndarray::{Array1, Array2};
+
+use crate::{quantile, Estimator};
+
+use super::Regressor;
+
+/// Constant predicted by a [`DummyRegressor`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// Mean of the targets.
+    Mean,
+    /// Median of the targets.
+    Median,
+    /// Quantile of the targets, between 0 and 1, interpolated linearly between targets.
+    Quantile(f64),
+    /// The given value.
+    Constant(f64),
+}
+
+/// Estimator which fits a [`DummyRegressor`], a baseline which ignores features and predicts a
+/// constant derived from the targets.
+///
+/// `fit` returns None if there are no targets, or the quantile is not between 0 and 1.
+///
+/// ```
+/// # use ndarray::{arr1, arr2};
+/// # use rs_ml::regression::dummy::{DummyRegressorEstimator, Strategy};
+/// # use rs_ml::Estimator;
+/// # use rs_ml::regression::Regressor;
+/// # fn test() -> Option<()> {
+/// let x = arr2(&[[0.], [1.], [2.], [3.]]);
+/// let y = arr1(&[1., 2., 3., 10.]);
+///
+/// let model = DummyRegressorEstimator::new(Strategy::Median).fit(&(&x, &y))?;
+///
+/// assert_eq!(model.constant(), 2.5);
+/// assert_eq!(model.predict(&arr2(&[[4.], [5.]]))?, arr1(&[2.5, 2.5]));
+/// # Some(())
+/// # }
+/// # fn main() {
+/// #   test().unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DummyRegressorEstimator {
+    strategy: Strategy,
+}
+
+/// Baseline regression model fitted by [`DummyRegressorEstimator`].
+#[derive(Debug, Clone, Copy)]
+pub struct DummyRegressor {
+    constant: f64,
+}
+
+impl Default for DummyRegressorEstimator {
+    fn default() -> Self {
+        DummyRegressorEstimator::new(Strategy::Mean)
+    }
+}
+
+impl DummyRegressorEstimator {
+    /// Create a new estimator predicting the constant given by `strategy`.
+    pub fn new(strategy: Strategy) -> Self {
+        DummyRegressorEstimator { strategy }
+    }
+
+    /// Set predicted constant.
+    pub fn with_strategy(self, strategy: Strategy) -> Self {
+        DummyRegressorEstimator { strategy }
+    }
+}
+
+impl Estimator<(&Array2<f64>, &Array1<f64>)> for DummyRegressorEstimator {
+    type Estimator = DummyRegressor;
+
+    fn fit(&self, input: &(&Array2<f64>, &Array1<f64>)) -> Option<Self::Estimator> {
+        let (_, y) = input;
+
+        let constant = match self.strategy {
+            Strategy::Mean => y.mean()?,
+            Strategy::Median => quantile(y.iter().copied(), 0.5)?,
+            Strategy::Quantile(q) => quantile(y.iter().copied(), q)?,
+            Strategy::Constant(constant) => match y.is_empty() {
+                true => return None,
+                false => constant,
+            },
+        };
+
+        Some(DummyRegressor { constant })
+    }
+}
+
+impl DummyRegressor {
+    /// Constant predicted for every record.
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+}
+
+impl Regressor<Array2<f64>, Array1<f64>> for DummyRegressor {
+    fn predict(&self, input: &Array2<f64>) -> Option<Array1<f64>> {
+        Some(Array1::from_elem(input.nrows(), self.constant))
+    }
+}
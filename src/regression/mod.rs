@@ -1,5 +1,6 @@
 //! Commonly used regression models.
 
+pub mod dummy;
 pub mod linear;
 
 /// Trait to interface with a fitted regression model.
@@ -0,0 +1,474 @@
+//! Resampling of imbalanced classification datasets.
+
+use ndarray::{Array1, Array2};
+use rand::{rngs::StdRng, seq::index::sample, Rng};
+
+use crate::{
+    classification::{neighbors::Metric, ClassificationDataSet},
+    seeded_rng, Estimatable,
+};
+
+/// Number of records each label is resampled to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Strategy<Label> {
+    /// Over-sample every label to the number of records of the most frequent label, or
+    /// under-sample every label to the number of records of the least frequent label.
+    Auto,
+    /// Over-sample labels to at least this fraction of the number of records of the most frequent
+    /// label, or under-sample labels so the least frequent label makes up at least this fraction
+    /// of their number of records. Must be between 0 (exclusive) and 1.
+    Ratio(f64),
+    /// [`Strategy::Ratio`] per label. Labels which are not listed are not resampled.
+    PerLabel(Vec<(Label, f64)>),
+}
+
+/// Trait to rebalance the labels of a classification dataset, for training on imbalanced data.
+pub trait Resampler<Features, Label> {
+    /// Resampled dataset with the original records kept in order, followed by new records. Returns
+    /// None if the dataset is empty or the resampler is invalid for it.
+    fn resample(
+        &self,
+        input: &ClassificationDataSet<Features, Label>,
+    ) -> Option<ClassificationDataSet<Array1<f64>, Label>>;
+}
+
+/// Resampler duplicating randomly drawn records of labels with too few records.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::classification::ClassificationDataSet;
+/// use rs_ml::sampling::{RandomOverSampler, Resampler};
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+///     (arr1(&[0.]), false),
+///     (arr1(&[1.]), false),
+///     (arr1(&[2.]), false),
+///     (arr1(&[3.]), true),
+/// ]);
+///
+/// let resampled = RandomOverSampler::default()
+///     .with_seed(Some(0))
+///     .resample(&dataset)
+///     .unwrap();
+///
+/// let positives = resampled.get_labels().into_iter().filter(|label| **label).count();
+///
+/// assert_eq!(positives, 3);
+/// assert_eq!(resampled.get_records().len(), 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RandomOverSampler<Label> {
+    strategy: Strategy<Label>,
+    seed: Option<u64>,
+}
+
+/// Resampler dropping randomly drawn records of labels with too many records.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::classification::ClassificationDataSet;
+/// use rs_ml::sampling::{RandomUnderSampler, Resampler, Strategy};
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+///     (arr1(&[0.]), false),
+///     (arr1(&[1.]), false),
+///     (arr1(&[2.]), false),
+///     (arr1(&[3.]), false),
+///     (arr1(&[4.]), true),
+/// ]);
+///
+/// let resampled = RandomUnderSampler::new(Strategy::Ratio(0.5))
+///     .with_seed(Some(0))
+///     .resample(&dataset)
+///     .unwrap();
+///
+/// assert_eq!(resampled.get_records().len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RandomUnderSampler<Label> {
+    strategy: Strategy<Label>,
+    seed: Option<u64>,
+}
+
+/// Resampler synthesizing records of labels with too few records with SMOTE (Chawla et al.,
+/// 2002). Each new record lies at a random point on the line between a randomly drawn record and
+/// one of its `k_neighbors` nearest records of the same label by euclidean distance, 5 by
+/// default.
+///
+/// Resampling fails if `k_neighbors` is zero, or a label to over-sample has a single record.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::classification::ClassificationDataSet;
+/// use rs_ml::sampling::{Resampler, SMOTE};
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+///     (arr1(&[0., 0.]), false),
+///     (arr1(&[1., 0.]), false),
+///     (arr1(&[2., 0.]), false),
+///     (arr1(&[3., 0.]), false),
+///     (arr1(&[5., 5.]), true),
+///     (arr1(&[6., 6.]), true),
+/// ]);
+///
+/// let resampled = SMOTE::default()
+///     .with_seed(Some(0))
+///     .resample(&dataset)
+///     .unwrap();
+///
+/// let synthetic = &resampled.get_records()[6..];
+///
+/// assert_eq!(synthetic.len(), 2);
+/// assert!(synthetic
+///     .iter()
+///     .all(|record| record.label && record.features[0] >= 5. && record.features[0] <= 6.));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SMOTE<Label> {
+    strategy: Strategy<Label>,
+    k_neighbors: usize,
+    seed: Option<u64>,
+}
+
+impl<Label> Default for RandomOverSampler<Label> {
+    fn default() -> Self {
+        RandomOverSampler::new(Strategy::Auto)
+    }
+}
+
+impl<Label> RandomOverSampler<Label> {
+    /// Create a new random over-sampler resampling labels according to `strategy`.
+    pub fn new(strategy: Strategy<Label>) -> Self {
+        RandomOverSampler {
+            strategy,
+            seed: None,
+        }
+    }
+
+    /// Set number of records each label is resampled to.
+    pub fn with_strategy(self, strategy: Strategy<Label>) -> Self {
+        RandomOverSampler { strategy, ..self }
+    }
+
+    /// Set seed to draw records with, or None to seed from OS entropy.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        RandomOverSampler { seed, ..self }
+    }
+}
+
+impl<Label> Default for RandomUnderSampler<Label> {
+    fn default() -> Self {
+        RandomUnderSampler::new(Strategy::Auto)
+    }
+}
+
+impl<Label> RandomUnderSampler<Label> {
+    /// Create a new random under-sampler resampling labels according to `strategy`.
+    pub fn new(strategy: Strategy<Label>) -> Self {
+        RandomUnderSampler {
+            strategy,
+            seed: None,
+        }
+    }
+
+    /// Set number of records each label is resampled to.
+    pub fn with_strategy(self, strategy: Strategy<Label>) -> Self {
+        RandomUnderSampler { strategy, ..self }
+    }
+
+    /// Set seed to draw records with, or None to seed from OS entropy.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        RandomUnderSampler { seed, ..self }
+    }
+}
+
+impl<Label> Default for SMOTE<Label> {
+    fn default() -> Self {
+        SMOTE::new(Strategy::Auto)
+    }
+}
+
+impl<Label> SMOTE<Label> {
+    /// Create a new SMOTE resampler over-sampling labels according to `strategy`.
+    pub fn new(strategy: Strategy<Label>) -> Self {
+        SMOTE {
+            strategy,
+            k_neighbors: 5,
+            seed: None,
+        }
+    }
+
+    /// Set number of records each label is resampled to.
+    pub fn with_strategy(self, strategy: Strategy<Label>) -> Self {
+        SMOTE { strategy, ..self }
+    }
+
+    /// Set number of nearest records of the same label to interpolate towards.
+    pub fn with_k_neighbors(self, k_neighbors: usize) -> Self {
+        SMOTE {
+            k_neighbors,
+            ..self
+        }
+    }
+
+    /// Set seed to draw records and interpolations with, or None to seed from OS entropy.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        SMOTE { seed, ..self }
+    }
+}
+
+impl<Label: PartialEq> Strategy<Label> {
+    /// Ratio of each label, or None if a ratio is invalid or a listed label is not in `labels`.
+    fn ratios(&self, labels: &[Label]) -> Option<Vec<Option<f64>>> {
+        let is_valid = |ratio: f64| ratio > 0. && ratio <= 1.;
+
+        match self {
+            Strategy::Auto => Some(vec![Some(1.); labels.len()]),
+            Strategy::Ratio(ratio) if is_valid(*ratio) => Some(vec![Some(*ratio); labels.len()]),
+            Strategy::Ratio(_) => None,
+            Strategy::PerLabel(ratios) => {
+                if ratios
+                    .iter()
+                    .any(|(label, ratio)| !is_valid(*ratio) || !labels.contains(label))
+                {
+                    return None;
+                }
+
+                let ratios = labels
+                    .iter()
+                    .map(|label| {
+                        ratios
+                            .iter()
+                            .find(|(l, _)| l == label)
+                            .map(|(_, ratio)| *ratio)
+                    })
+                    .collect();
+
+                Some(ratios)
+            }
+        }
+    }
+
+    /// Number of records of each label after over-sampling, never fewer than there are.
+    fn over_sampled_counts(&self, labels: &[Label], counts: &[usize]) -> Option<Vec<usize>> {
+        let majority = *counts.iter().max()? as f64;
+
+        let targets = self
+            .ratios(labels)?
+            .iter()
+            .zip(counts)
+            .map(|(ratio, count)| match ratio {
+                Some(ratio) => (*count).max((ratio * majority).round() as usize),
+                None => *count,
+            })
+            .collect();
+
+        Some(targets)
+    }
+
+    /// Number of records of each label after under-sampling, never more than there are.
+    fn under_sampled_counts(&self, labels: &[Label], counts: &[usize]) -> Option<Vec<usize>> {
+        let minority = *counts.iter().min()? as f64;
+
+        let targets = self
+            .ratios(labels)?
+            .iter()
+            .zip(counts)
+            .map(|(ratio, count)| match ratio {
+                Some(ratio) => (*count).min((minority / ratio).round() as usize),
+                None => *count,
+            })
+            .collect();
+
+        Some(targets)
+    }
+}
+
+/// Features, labels, label of each record, record indices per label and number of records per
+/// label of a dataset.
+struct Prepared<Label> {
+    features: Array2<f64>,
+    labels: Vec<Label>,
+    classes: Vec<usize>,
+    label_indices: Vec<Vec<usize>>,
+    counts: Vec<usize>,
+}
+
+fn prepare<Features: Estimatable, Label: PartialEq + Clone>(
+    input: &ClassificationDataSet<Features, Label>,
+) -> Option<Prepared<Label>> {
+    let features = input.features_array()?;
+    let labels = input.distinct_labels();
+    let classes = input.label_classes(&labels);
+    let label_indices = input.label_indices(&labels);
+    let counts = label_indices.iter().map(|indeces| indeces.len()).collect();
+
+    Some(Prepared {
+        features,
+        labels,
+        classes,
+        label_indices,
+        counts,
+    })
+}
+
+/// Dataset of the given records, followed by new records with their labels.
+fn assemble<Label: Clone>(
+    input: &Prepared<Label>,
+    kept: &[usize],
+    synthetic: Vec<(Array1<f64>, usize)>,
+) -> ClassificationDataSet<Array1<f64>, Label> {
+    let originals = kept
+        .iter()
+        .map(|idx| (input.features.row(*idx).to_owned(), input.classes[*idx]));
+
+    ClassificationDataSet::from(
+        originals
+            .chain(synthetic)
+            .map(|(features, class)| (features, input.labels[class].clone())),
+    )
+}
+
+impl<Features: Estimatable, Label: PartialEq + Clone> Resampler<Features, Label>
+    for RandomOverSampler<Label>
+{
+    fn resample(
+        &self,
+        input: &ClassificationDataSet<Features, Label>,
+    ) -> Option<ClassificationDataSet<Array1<f64>, Label>> {
+        let prepared = prepare(input)?;
+        let targets = self
+            .strategy
+            .over_sampled_counts(&prepared.labels, &prepared.counts)?;
+
+        let mut rng = seeded_rng(self.seed);
+        let mut duplicates = vec![];
+
+        for (class, (indeces, target)) in prepared.label_indices.iter().zip(&targets).enumerate() {
+            for _ in indeces.len()..*target {
+                let idx = indeces[rng.random_range(0..indeces.len())];
+                duplicates.push((prepared.features.row(idx).to_owned(), class));
+            }
+        }
+
+        let kept: Vec<usize> = (0..prepared.features.nrows()).collect();
+
+        Some(assemble(&prepared, &kept, duplicates))
+    }
+}
+
+impl<Features: Estimatable, Label: PartialEq + Clone> Resampler<Features, Label>
+    for RandomUnderSampler<Label>
+{
+    fn resample(
+        &self,
+        input: &ClassificationDataSet<Features, Label>,
+    ) -> Option<ClassificationDataSet<Array1<f64>, Label>> {
+        let prepared = prepare(input)?;
+        let targets = self
+            .strategy
+            .under_sampled_counts(&prepared.labels, &prepared.counts)?;
+
+        let mut rng = seeded_rng(self.seed);
+        let mut kept = vec![];
+
+        for (indeces, target) in prepared.label_indices.iter().zip(&targets) {
+            kept.extend(
+                sample(&mut rng, indeces.len(), *target)
+                    .into_iter()
+                    .map(|position| indeces[position]),
+            );
+        }
+
+        kept.sort_unstable();
+
+        Some(assemble(&prepared, &kept, vec![]))
+    }
+}
+
+/// Positions within `indeces` of the `k` records nearest to each record, excluding itself.
+fn nearest_neighbors(features: &Array2<f64>, indeces: &[usize], k: usize) -> Vec<Vec<usize>> {
+    indeces
+        .iter()
+        .map(|a| {
+            let mut distances: Vec<(usize, f64)> = indeces
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| *b != a)
+                .map(|(position, b)| {
+                    let distance = Metric::Euclidean.distance(features.row(*a), features.row(*b));
+                    (position, distance)
+                })
+                .collect();
+
+            distances.sort_by(|a, b| a.1.total_cmp(&b.1));
+            distances.truncate(k);
+
+            distances
+                .into_iter()
+                .map(|(position, _)| position)
+                .collect()
+        })
+        .collect()
+}
+
+/// New record on the line between a randomly drawn record and a random one of its neighbors.
+fn interpolate(
+    features: &Array2<f64>,
+    indeces: &[usize],
+    neighbors: &[Vec<usize>],
+    rng: &mut StdRng,
+) -> Option<Array1<f64>> {
+    let position = rng.random_range(0..indeces.len());
+    let candidates = &neighbors[position];
+    let neighbor = *candidates.get(rng.random_range(0..candidates.len()))?;
+
+    let origin = features.row(indeces[position]);
+    let gap: f64 = rng.random_range(0.0..1.0);
+
+    Some(&origin + &((&features.row(indeces[neighbor]) - &origin) * gap))
+}
+
+impl<Features: Estimatable, Label: PartialEq + Clone> Resampler<Features, Label> for SMOTE<Label> {
+    fn resample(
+        &self,
+        input: &ClassificationDataSet<Features, Label>,
+    ) -> Option<ClassificationDataSet<Array1<f64>, Label>> {
+        if self.k_neighbors == 0 {
+            return None;
+        }
+
+        let prepared = prepare(input)?;
+        let targets = self
+            .strategy
+            .over_sampled_counts(&prepared.labels, &prepared.counts)?;
+
+        let mut rng = seeded_rng(self.seed);
+        let mut synthetic = vec![];
+
+        for (class, (indeces, target)) in prepared.label_indices.iter().zip(&targets).enumerate() {
+            if *target == indeces.len() {
+                continue;
+            }
+
+            // a single record has no neighbors to interpolate towards
+            if indeces.len() < 2 {
+                return None;
+            }
+
+            let neighbors = nearest_neighbors(&prepared.features, indeces, self.k_neighbors);
+
+            for _ in indeces.len()..*target {
+                let record = interpolate(&prepared.features, indeces, &neighbors, &mut rng)?;
+                synthetic.push((record, class));
+            }
+        }
+
+        let kept: Vec<usize> = (0..prepared.features.nrows()).collect();
+
+        Some(assemble(&prepared, &kept, synthetic))
+    }
+}
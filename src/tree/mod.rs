@@ -0,0 +1,670 @@
+//! Decision tree models.
+
+use std::{fmt::Display, fmt::Write, marker::PhantomData};
+
+use ndarray::{Array1, Array2, ArrayView1};
+use rand::{rngs::StdRng, seq::index::sample};
+
+use crate::{
+    argmax,
+    classification::{to_feature_matrix, ClassificationDataSet, Classifier},
+    seeded_rng, Estimatable, Estimator, WeightedEstimator,
+};
+
+/// Impurity measure used to pick the best split of a classification tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Criterion {
+    /// Gini impurity, the probability of mislabeling a record by drawing a label from the node.
+    Gini,
+    /// Shannon entropy of the labels in a node.
+    Entropy,
+}
+
+/// Number of features randomly drawn as split candidates at each node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaxFeatures {
+    /// Consider all features.
+    All,
+    /// Square root of the number of features.
+    Sqrt,
+    /// Base 2 logarithm of the number of features.
+    Log2,
+    /// Fixed number of features, at most the number of features.
+    Count(usize),
+    /// Fraction of the features, between 0 and 1.
+    Fraction(f64),
+}
+
+impl MaxFeatures {
+    /// Number of features to draw out of `nfeatures`, or None if invalid.
+    pub(crate) fn resolve(&self, nfeatures: usize) -> Option<usize> {
+        let count = match self {
+            MaxFeatures::All => nfeatures,
+            MaxFeatures::Sqrt => (nfeatures as f64).sqrt() as usize,
+            MaxFeatures::Log2 => (nfeatures as f64).log2() as usize,
+            MaxFeatures::Count(0) => return None,
+            MaxFeatures::Count(count) => *count,
+            MaxFeatures::Fraction(fraction) if *fraction <= 0. || *fraction > 1. => return None,
+            MaxFeatures::Fraction(fraction) => (fraction * nfeatures as f64) as usize,
+        };
+
+        Some(count.clamp(1, nfeatures.max(1)))
+    }
+}
+
+/// Estimator to train a [`DecisionTreeClassifier`] with the CART algorithm.
+///
+/// By default, nodes are split on the [`Criterion::Gini`] impurity over all features until they
+/// are pure, with at least 2 records to split a node and at least 1 record per leaf. Fitting fails
+/// if these hyperparameters are zero.
+///
+/// Example:
+/// ```
+/// use ndarray::{arr1, Array1};
+/// use rs_ml::Estimator;
+/// use rs_ml::classification::{ClassificationDataSet, Classifier};
+/// use rs_ml::tree::{Criterion, DecisionTreeClassifierEstimator};
+///
+/// let dataset: ClassificationDataSet<Array1<f64>, &str> = ClassificationDataSet::from(vec![
+///     (arr1(&[1., 0.]), "low"),
+///     (arr1(&[2., 1.]), "low"),
+///     (arr1(&[3., 0.]), "medium"),
+///     (arr1(&[4., 1.]), "medium"),
+///     (arr1(&[5., 1.]), "high"),
+/// ]);
+///
+/// let model = DecisionTreeClassifierEstimator::default()
+///     .with_criterion(Criterion::Entropy)
+///     .with_max_depth(Some(2))
+///     .fit(&dataset)
+///     .unwrap();
+///
+/// let predictions = model.predict([arr1(&[1.5, 0.])].into_iter()).unwrap();
+///
+/// assert_eq!(predictions, vec!["low"]);
+/// println!("{}", model.export_text(Some(&["size", "flag"])));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DecisionTreeClassifierEstimator {
+    criterion: Criterion,
+    max_depth: Option<usize>,
+    min_samples_split: usize,
+    min_samples_leaf: usize,
+    max_features: MaxFeatures,
+    seed: Option<u64>,
+}
+
+impl Default for DecisionTreeClassifierEstimator {
+    fn default() -> Self {
+        DecisionTreeClassifierEstimator {
+            criterion: Criterion::Gini,
+            max_depth: None,
+            min_samples_split: 2,
+            min_samples_leaf: 1,
+            max_features: MaxFeatures::All,
+            seed: None,
+        }
+    }
+}
+
+impl DecisionTreeClassifierEstimator {
+    /// Set impurity criterion.
+    pub fn with_criterion(self, criterion: Criterion) -> Self {
+        DecisionTreeClassifierEstimator { criterion, ..self }
+    }
+
+    /// Set maximum depth of the tree, or None to split until leaves are pure.
+    pub fn with_max_depth(self, max_depth: Option<usize>) -> Self {
+        DecisionTreeClassifierEstimator { max_depth, ..self }
+    }
+
+    /// Set minimum number of records required to split a node.
+    pub fn with_min_samples_split(self, min_samples_split: usize) -> Self {
+        DecisionTreeClassifierEstimator {
+            min_samples_split,
+            ..self
+        }
+    }
+
+    /// Set minimum number of records in each leaf.
+    pub fn with_min_samples_leaf(self, min_samples_leaf: usize) -> Self {
+        DecisionTreeClassifierEstimator {
+            min_samples_leaf,
+            ..self
+        }
+    }
+
+    /// Set number of features considered at each split.
+    pub fn with_max_features(self, max_features: MaxFeatures) -> Self {
+        DecisionTreeClassifierEstimator {
+            max_features,
+            ..self
+        }
+    }
+
+    /// Set seed to draw split candidates with, or None to seed from OS entropy. Only used if
+    /// fewer than all features are considered at each split.
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        DecisionTreeClassifierEstimator { seed, ..self }
+    }
+
+    /// Grow a tree over the given class indices and record weights.
+    pub(crate) fn build(
+        &self,
+        features: &Array2<f64>,
+        classes: &[usize],
+        nclasses: usize,
+        weights: &[f64],
+        rng: &mut StdRng,
+    ) -> Option<Tree> {
+        let criterion = match self.criterion {
+            Criterion::Gini => SplitCriterion::Gini,
+            Criterion::Entropy => SplitCriterion::Entropy,
+        };
+
+        let params = TreeParams {
+            criterion,
+            max_depth: self.max_depth,
+            min_samples_split: self.min_samples_split,
+            min_samples_leaf: self.min_samples_leaf,
+            max_features: self.max_features.resolve(features.ncols())?,
+        };
+
+        params.build(features, &Targets::Classes(classes, nclasses), weights, rng)
+    }
+}
+
+/// Represents a fitted decision tree classifier. Created with the `fit()` function implemented
+/// for [`DecisionTreeClassifierEstimator`].
+#[derive(Debug)]
+pub struct DecisionTreeClassifier<Input, Label> {
+    _input: PhantomData<Input>,
+    tree: Tree,
+    labels: Vec<Label>,
+}
+
+/// Impurity measure of a node, over the statistics of the records in the node. Classification
+/// statistics are the weight of each class, regression statistics are the total weight, the
+/// weighted sum of targets and the weighted sum of squared targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SplitCriterion {
+    Gini,
+    Entropy,
+    SquaredError,
+}
+
+impl SplitCriterion {
+    fn impurity(&self, stats: &[f64]) -> f64 {
+        let total = self.weight(stats);
+
+        if total <= 0. {
+            return 0.;
+        }
+
+        match self {
+            SplitCriterion::Gini => 1. - stats.iter().map(|w| (w / total).powi(2)).sum::<f64>(),
+            SplitCriterion::Entropy => -stats
+                .iter()
+                .filter(|w| **w > 0.)
+                .map(|w| (w / total) * (w / total).log2())
+                .sum::<f64>(),
+            SplitCriterion::SquaredError => (stats[2] / total - (stats[1] / total).powi(2)).max(0.),
+        }
+    }
+
+    /// Prediction of a node: the distribution of classes, or the mean target.
+    fn value(&self, stats: &[f64]) -> Array1<f64> {
+        let total = self.weight(stats);
+
+        match self {
+            SplitCriterion::Gini | SplitCriterion::Entropy => {
+                stats.iter().map(|w| w / total).collect()
+            }
+            SplitCriterion::SquaredError => Array1::from_elem(1, stats[1] / total),
+        }
+    }
+
+    /// Total weight of the records summarized in the statistics.
+    fn weight(&self, stats: &[f64]) -> f64 {
+        match self {
+            SplitCriterion::Gini | SplitCriterion::Entropy => stats.iter().sum(),
+            SplitCriterion::SquaredError => stats[0],
+        }
+    }
+}
+
+/// Targets a tree is fitted to.
+pub(crate) enum Targets<'a> {
+    /// Class index of each record, and the number of classes.
+    Classes(&'a [usize], usize),
+    /// Real valued target of each record.
+    Values(&'a [f64]),
+}
+
+impl Targets<'_> {
+    fn nstats(&self) -> usize {
+        match self {
+            Targets::Classes(_, nclasses) => *nclasses,
+            Targets::Values(_) => 3,
+        }
+    }
+
+    /// Add a weighted record to the statistics of a node.
+    fn add(&self, stats: &mut [f64], idx: usize, weight: f64) {
+        match self {
+            Targets::Classes(classes, _) => stats[classes[idx]] += weight,
+            Targets::Values(values) => {
+                stats[0] += weight;
+                stats[1] += weight * values[idx];
+                stats[2] += weight * values[idx].powi(2);
+            }
+        }
+    }
+}
+
+/// Resolved hyperparameters to grow a tree with.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TreeParams {
+    pub(crate) criterion: SplitCriterion,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) min_samples_split: usize,
+    pub(crate) min_samples_leaf: usize,
+    pub(crate) max_features: usize,
+}
+
+/// Node of a fitted tree. Split nodes send records with `feature <= threshold` to the left child.
+#[derive(Debug, Clone)]
+pub(crate) struct Node {
+    pub(crate) value: Array1<f64>,
+    impurity: f64,
+    nsamples: usize,
+    split: Option<Split>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Split {
+    feature: usize,
+    threshold: f64,
+    left: usize,
+    right: usize,
+}
+
+/// Fitted binary tree, with the root as first node.
+#[derive(Debug, Clone)]
+pub(crate) struct Tree {
+    pub(crate) nodes: Vec<Node>,
+    nfeatures: usize,
+    importances: Array1<f64>,
+}
+
+/// Best split found for a node.
+struct Candidate {
+    feature: usize,
+    threshold: f64,
+    proxy: f64,
+}
+
+impl TreeParams {
+    /// Grow a tree. Records with zero weight are ignored. Returns None if the hyperparameters are
+    /// invalid, the weights do not match the records, or there are no records with weight.
+    pub(crate) fn build(
+        &self,
+        features: &Array2<f64>,
+        targets: &Targets,
+        weights: &[f64],
+        rng: &mut StdRng,
+    ) -> Option<Tree> {
+        if self.min_samples_split < 2 || self.min_samples_leaf == 0 || self.max_features == 0 {
+            return None;
+        }
+
+        if weights.len() != features.nrows() || weights.iter().any(|w| *w < 0.) {
+            return None;
+        }
+
+        let indeces: Vec<usize> = (0..features.nrows()).filter(|i| weights[*i] > 0.).collect();
+
+        if indeces.is_empty() {
+            return None;
+        }
+
+        let mut tree = Tree {
+            nodes: vec![],
+            nfeatures: features.ncols(),
+            importances: Array1::zeros(features.ncols()),
+        };
+
+        self.grow(&mut tree, features, targets, weights, indeces, 0, rng);
+
+        Some(tree)
+    }
+
+    fn stats(&self, targets: &Targets, weights: &[f64], indeces: &[usize]) -> Vec<f64> {
+        let mut stats = vec![0.; targets.nstats()];
+
+        for idx in indeces {
+            targets.add(&mut stats, *idx, weights[*idx]);
+        }
+
+        stats
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn grow(
+        &self,
+        tree: &mut Tree,
+        features: &Array2<f64>,
+        targets: &Targets,
+        weights: &[f64],
+        indeces: Vec<usize>,
+        depth: usize,
+        rng: &mut StdRng,
+    ) -> usize {
+        let stats = self.stats(targets, weights, &indeces);
+        let impurity = self.criterion.impurity(&stats);
+
+        let node = tree.nodes.len();
+        tree.nodes.push(Node {
+            value: self.criterion.value(&stats),
+            impurity,
+            nsamples: indeces.len(),
+            split: None,
+        });
+
+        let can_split = self.max_depth.is_none_or(|max_depth| depth < max_depth)
+            && indeces.len() >= self.min_samples_split
+            && indeces.len() >= 2 * self.min_samples_leaf
+            && impurity > 1e-12;
+
+        if !can_split {
+            return node;
+        }
+
+        let Some(candidate) = self.best_split(features, targets, weights, &indeces, rng) else {
+            return node;
+        };
+
+        let (left, right): (Vec<usize>, Vec<usize>) = indeces
+            .into_iter()
+            .partition(|i| features[(*i, candidate.feature)] <= candidate.threshold);
+
+        let weight = self.criterion.weight(&stats);
+        tree.importances[candidate.feature] += weight * impurity - candidate.proxy;
+
+        let left = self.grow(tree, features, targets, weights, left, depth + 1, rng);
+        let right = self.grow(tree, features, targets, weights, right, depth + 1, rng);
+
+        tree.nodes[node].split = Some(Split {
+            feature: candidate.feature,
+            threshold: candidate.threshold,
+            left,
+            right,
+        });
+
+        node
+    }
+
+    /// Find the split with the lowest weighted impurity of its children among a random subset
+    /// of features.
+    fn best_split(
+        &self,
+        features: &Array2<f64>,
+        targets: &Targets,
+        weights: &[f64],
+        indeces: &[usize],
+        rng: &mut StdRng,
+    ) -> Option<Candidate> {
+        let nfeatures = features.ncols();
+
+        let candidates: Vec<usize> = match self.max_features < nfeatures {
+            true => sample(rng, nfeatures, self.max_features).into_vec(),
+            false => (0..nfeatures).collect(),
+        };
+
+        let total = self.stats(targets, weights, indeces);
+        let mut best: Option<Candidate> = None;
+        let mut sorted = indeces.to_vec();
+
+        for feature in candidates {
+            sorted.sort_by(|a, b| features[(*a, feature)].total_cmp(&features[(*b, feature)]));
+
+            let mut left = vec![0.; total.len()];
+
+            for (position, pair) in sorted.windows(2).enumerate() {
+                targets.add(&mut left, pair[0], weights[pair[0]]);
+
+                let nleft = position + 1;
+                let (current, next) = (features[(pair[0], feature)], features[(pair[1], feature)]);
+
+                if current >= next
+                    || nleft < self.min_samples_leaf
+                    || sorted.len() - nleft < self.min_samples_leaf
+                {
+                    continue;
+                }
+
+                let right: Vec<f64> = total.iter().zip(&left).map(|(t, l)| t - l).collect();
+
+                let proxy = self.criterion.weight(&left) * self.criterion.impurity(&left)
+                    + self.criterion.weight(&right) * self.criterion.impurity(&right);
+
+                if best.as_ref().is_none_or(|best| proxy < best.proxy) {
+                    let threshold = current + (next - current) / 2.;
+
+                    best = Some(Candidate {
+                        feature,
+                        // guard against the midpoint rounding up to the next value
+                        threshold: match threshold < next {
+                            true => threshold,
+                            false => current,
+                        },
+                        proxy,
+                    });
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl Node {
+    pub(crate) fn is_leaf(&self) -> bool {
+        self.split.is_none()
+    }
+}
+
+impl Tree {
+    /// Index of the leaf a record ends up in.
+    pub(crate) fn leaf(&self, record: ArrayView1<f64>) -> usize {
+        let mut node = 0;
+
+        while let Some(split) = &self.nodes[node].split {
+            node = match record[split.feature] <= split.threshold {
+                true => split.left,
+                false => split.right,
+            };
+        }
+
+        node
+    }
+
+    /// Prediction of the leaf a record ends up in.
+    pub(crate) fn value(&self, record: ArrayView1<f64>) -> &Array1<f64> {
+        &self.nodes[self.leaf(record)].value
+    }
+
+    /// Number of features the tree was fitted on.
+    pub(crate) fn nfeatures(&self) -> usize {
+        self.nfeatures
+    }
+
+    /// Total impurity decrease of the splits on each feature, normalized to sum to one.
+    pub(crate) fn feature_importances(&self) -> Array1<f64> {
+        let total = self.importances.sum();
+
+        match total > 0. {
+            true => &self.importances / total,
+            false => Array1::zeros(self.nfeatures),
+        }
+    }
+
+    fn write_node<F, G>(
+        &self,
+        out: &mut String,
+        node: usize,
+        depth: usize,
+        feature_name: &F,
+        leaf: &G,
+    ) where
+        F: Fn(usize) -> String,
+        G: Fn(&Node) -> String,
+    {
+        let indent = "|   ".repeat(depth);
+
+        match &self.nodes[node].split {
+            Some(split) => {
+                let name = feature_name(split.feature);
+
+                let _ = writeln!(out, "{indent}|--- {name} <= {:.4}", split.threshold);
+                self.write_node(out, split.left, depth + 1, feature_name, leaf);
+                let _ = writeln!(out, "{indent}|--- {name} >  {:.4}", split.threshold);
+                self.write_node(out, split.right, depth + 1, feature_name, leaf);
+            }
+            None => {
+                let _ = writeln!(out, "{indent}|--- {}", leaf(&self.nodes[node]));
+            }
+        }
+    }
+}
+
+impl<Input, Label> DecisionTreeClassifier<Input, Label> {
+    /// Impurity based importance of each feature: the total weighted impurity decrease of the
+    /// splits on the feature, normalized to sum to one.
+    pub fn feature_importances(&self) -> Array1<f64> {
+        self.tree.feature_importances()
+    }
+
+    /// Depth of the tree. A tree with only a root leaf has depth zero.
+    pub fn depth(&self) -> usize {
+        fn depth(tree: &Tree, node: usize) -> usize {
+            match &tree.nodes[node].split {
+                Some(split) => 1 + depth(tree, split.left).max(depth(tree, split.right)),
+                None => 0,
+            }
+        }
+
+        depth(&self.tree, 0)
+    }
+
+    /// Number of leaves in the tree.
+    pub fn leaf_count(&self) -> usize {
+        self.tree.nodes.iter().filter(|node| node.is_leaf()).count()
+    }
+}
+
+impl<Input, Label: Display> DecisionTreeClassifier<Input, Label> {
+    /// Human readable dump of the tree, with one line per branch and leaf. Features are named by
+    /// `feature_names` if given, otherwise by their index. Leaves show the most likely label,
+    /// its probability, the number of training records and the impurity of the leaf.
+    ///
+    /// ```text
+    /// |--- petal_length <= 2.4500
+    /// |   |--- class: setosa (p = 1.00, samples = 50, impurity = 0.0000)
+    /// |--- petal_length >  2.4500
+    /// ...
+    /// ```
+    pub fn export_text(&self, feature_names: Option<&[&str]>) -> String {
+        let mut out = String::new();
+
+        let feature_name = |feature: usize| match feature_names.and_then(|names| names.get(feature))
+        {
+            Some(name) => name.to_string(),
+            None => format!("feature_{feature}"),
+        };
+
+        let leaf = |node: &Node| {
+            let idx = argmax(node.value.iter().copied()).unwrap_or(0);
+            let p = node.value[idx];
+
+            format!(
+                "class: {} (p = {:.2}, samples = {}, impurity = {:.4})",
+                self.labels[idx], p, node.nsamples, node.impurity
+            )
+        };
+
+        self.tree.write_node(&mut out, 0, 0, &feature_name, &leaf);
+
+        out
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone> Estimator<ClassificationDataSet<Input, Label>>
+    for DecisionTreeClassifierEstimator
+{
+    type Estimator = DecisionTreeClassifier<Input, Label>;
+
+    fn fit(&self, input: &ClassificationDataSet<Input, Label>) -> Option<Self::Estimator> {
+        self.fit_weighted(input, &vec![1.; input.get_records().len()])
+    }
+}
+
+impl<Input: Estimatable, Label: PartialEq + Clone>
+    WeightedEstimator<ClassificationDataSet<Input, Label>> for DecisionTreeClassifierEstimator
+{
+    /// Fit a [`DecisionTreeClassifier`] with a weight per record. Impurities and leaf
+    /// probabilities are weighted, and records without weight are ignored. Returns None if the
+    /// number of weights does not match the number of records, any weight is negative, or no
+    /// record has weight.
+    fn fit_weighted(
+        &self,
+        input: &ClassificationDataSet<Input, Label>,
+        sample_weights: &[f64],
+    ) -> Option<Self::Estimator> {
+        let distinct_labels = input.distinct_labels();
+        let features = input.features_array()?;
+        let classes = input.label_classes(&distinct_labels);
+
+        let mut rng = seeded_rng(self.seed);
+
+        let tree = self.build(
+            &features,
+            &classes,
+            distinct_labels.len(),
+            sample_weights,
+            &mut rng,
+        )?;
+
+        Some(DecisionTreeClassifier {
+            _input: PhantomData,
+            tree,
+            labels: distinct_labels,
+        })
+    }
+}
+
+impl<Input: Estimatable, Label: Clone> Classifier<Input, Label>
+    for DecisionTreeClassifier<Input, Label>
+{
+    fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+    where
+        I: Iterator<Item = Input>,
+    {
+        let features = to_feature_matrix(
+            arr.map(|record| record.prepare_for_estimation()),
+            self.tree.nfeatures(),
+        )?;
+
+        let mut proba = Array2::zeros((features.nrows(), self.labels.len()));
+
+        for (mut row, record) in proba.rows_mut().into_iter().zip(features.rows()) {
+            row.assign(self.tree.value(record));
+        }
+
+        Some(proba)
+    }
+}
@@ -37,7 +37,7 @@ fn iris() -> Result<(), Box<dyn Error>> {
 
     let (train_dataset, test_dataset) = train_test_split(dataset, 0.25);
 
-    let model = GaussianNBEstimator
+    let model = GaussianNBEstimator::default()
         .fit(&train_dataset)
         .ok_or("Training failed")?;
     let inference = model
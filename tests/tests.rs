@@ -8,13 +8,64 @@ use ndarray::Array1;
 use ndarray::Array2;
 use ndarray::Axis;
 use num_derive::ToPrimitive;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+use rs_ml::classification::calibration::CalibratedClassifierEstimator;
+use rs_ml::classification::calibration::Method;
+use rs_ml::classification::discriminant_analysis::LinearDiscriminantAnalysisEstimator;
+use rs_ml::classification::discriminant_analysis::QuadraticDiscriminantAnalysisEstimator;
+use rs_ml::classification::dummy::DummyClassifierEstimator;
+use rs_ml::classification::dummy::Strategy;
+use rs_ml::classification::logistic::LogisticRegressionEstimator;
+use rs_ml::classification::logistic::Penalty;
+use rs_ml::classification::logistic::Solver;
+use rs_ml::classification::multiclass::OneVsOneEstimator;
+use rs_ml::classification::multiclass::OneVsRestEstimator;
+use rs_ml::classification::multilabel::BinaryRelevanceEstimator;
+use rs_ml::classification::multilabel::ClassifierChainEstimator;
+use rs_ml::classification::multilabel::MultiLabelClassifier;
+use rs_ml::classification::multilabel::MultiLabelDataSet;
+use rs_ml::classification::naive_bayes::BernoulliNBEstimator;
+use rs_ml::classification::naive_bayes::CategoricalNBEstimator;
+use rs_ml::classification::naive_bayes::ComplementNBEstimator;
+use rs_ml::classification::naive_bayes::GaussianNB;
 use rs_ml::classification::naive_bayes::GaussianNBEstimator;
+use rs_ml::classification::naive_bayes::MultinomialNBEstimator;
+use rs_ml::classification::neighbors::Algorithm;
+use rs_ml::classification::neighbors::KNeighborsClassifierEstimator;
+use rs_ml::classification::neighbors::Metric;
+use rs_ml::classification::neighbors::NearestCentroidEstimator;
+use rs_ml::classification::neighbors::Weights;
+use rs_ml::classification::svm::Kernel;
+use rs_ml::classification::svm::LinearSVCEstimator;
+use rs_ml::classification::svm::Loss;
+use rs_ml::classification::svm::SVCEstimator;
 use rs_ml::classification::ClassificationDataSet;
 use rs_ml::classification::ClassificationRecord;
 use rs_ml::classification::Classifier;
 use rs_ml::dimensionality_reduction::pca::PCAEstimator;
+use rs_ml::ensemble::adaboost::AdaBoostClassifierEstimator;
+use rs_ml::ensemble::forest::RandomForestClassifierEstimator;
+use rs_ml::ensemble::gradient_boosting::GradientBoostingClassifierEstimator;
+use rs_ml::ensemble::stacking::StackingClassifierEstimator;
+use rs_ml::ensemble::voting::Voting;
+use rs_ml::ensemble::voting::VotingClassifierEstimator;
+use rs_ml::metrics::accuracy;
+use rs_ml::metrics::hamming_loss;
+use rs_ml::metrics::subset_accuracy;
+use rs_ml::neural_network::Activation;
+use rs_ml::neural_network::MLPClassifierEstimator;
+use rs_ml::neural_network::Optimizer;
+use rs_ml::regression::dummy::DummyRegressorEstimator;
+use rs_ml::regression::dummy::Strategy as RegressionStrategy;
 use rs_ml::regression::linear::OrdinaryLeastSquaresEstimator;
 use rs_ml::regression::Regressor;
+use rs_ml::sampling::RandomOverSampler;
+use rs_ml::sampling::RandomUnderSampler;
+use rs_ml::sampling::Resampler;
+use rs_ml::sampling::Strategy as SamplingStrategy;
+use rs_ml::sampling::SMOTE;
 use rs_ml::transformer::embedding::OneHotEmbeddingEstimator;
 use rs_ml::transformer::embedding::OneHotEmbeddingTransformer;
 use rs_ml::transformer::embedding::OrderedEnumEmbeddingTransformer;
@@ -22,7 +73,12 @@ use rs_ml::transformer::scalers::MinMaxScalerParams;
 use rs_ml::transformer::scalers::StandardScalerEstimator;
 use rs_ml::transformer::FitTransform;
 use rs_ml::transformer::Transformer;
+use rs_ml::tree::Criterion;
+use rs_ml::tree::DecisionTreeClassifierEstimator;
+use rs_ml::tree::MaxFeatures;
+use rs_ml::Estimatable;
 use rs_ml::Estimator;
+use rs_ml::WeightedEstimator;
 
 #[test]
 fn gaussian_nb() {
@@ -45,9 +101,21 @@ fn gaussian_nb() {
 
     let dataset: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(records);
 
-    let model = GaussianNBEstimator.fit(&dataset).unwrap();
+    let model = GaussianNBEstimator::default().fit(&dataset).unwrap();
 
     model.predict(arr.rows().into_iter().map(|row| row.to_owned()));
+
+    let model = GaussianNBEstimator::new(0.).fit(&dataset).unwrap();
+
+    // population variances, e.g. the first feature of `true` is [0, 3] with variance 9 / 4
+    assert!(model.means().abs_diff_eq(
+        &arr2(&[[1.5, 1.5, 6.], [19. / 3., 103. / 3., 193. / 3.]]),
+        1e-9
+    ));
+    assert!(model.variances().abs_diff_eq(
+        &arr2(&[[2.25, 0.25, 16.], [86. / 9., 8918. / 9., 17078. / 9.]]),
+        1e-9
+    ));
 }
 
 #[test]
@@ -187,3 +255,2109 @@ fn test_ordered_enum_encoding() {
 
     assert_eq!(transformed, Some(arr1(&[0, 1, 2])))
 }
+
+#[test]
+fn multinomial_nb() {
+    let arr = arr2(&[
+        [5., 0., 1.],
+        [3., 1., 0.],
+        [0., 4., 2.],
+        [1., 6., 0.],
+        [0., 0., 7.],
+        [1., 0., 5.],
+    ]);
+
+    let labels = vec![0, 0, 1, 1, 2, 2];
+
+    let dataset: ClassificationDataSet<Array1<f64>, i32> =
+        ClassificationDataSet::from(arr.rows().into_iter().map(|row| row.to_owned()).zip(labels));
+
+    let model = MultinomialNBEstimator::default().fit(&dataset).unwrap();
+
+    let proba = model
+        .predict_proba(arr.rows().into_iter().map(|row| row.to_owned()))
+        .unwrap();
+
+    assert!(proba
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+
+    let predictions = model
+        .predict(arr.rows().into_iter().map(|row| row.to_owned()))
+        .unwrap();
+
+    assert_eq!(predictions, vec![0, 0, 1, 1, 2, 2]);
+
+    assert!(MultinomialNBEstimator::new(0.).fit(&dataset).is_none());
+}
+
+#[test]
+fn bernoulli_nb() {
+    let arr = arr2(&[
+        [1., 1., 0., 0.],
+        [1., 0., 0., 0.],
+        [1., 1., 1., 0.],
+        [0., 0., 1., 1.],
+        [0., 1., 1., 1.],
+        [0., 0., 0., 1.],
+    ]);
+
+    let labels = vec![true, true, true, false, false, false];
+
+    let dataset: ClassificationDataSet<Array1<f64>, bool> =
+        ClassificationDataSet::from(arr.rows().into_iter().map(|row| row.to_owned()).zip(labels));
+
+    let model = BernoulliNBEstimator::default().fit(&dataset).unwrap();
+
+    let proba = model
+        .predict_proba(arr.rows().into_iter().map(|row| row.to_owned()))
+        .unwrap();
+
+    assert_eq!(proba.dim(), (6, 2));
+    assert!(proba
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+
+    let predictions = model
+        .predict([arr1(&[1., 0., 0., 0.]), arr1(&[0., 0., 0., 1.])].into_iter())
+        .unwrap();
+
+    assert_eq!(predictions, vec![true, false]);
+}
+
+#[test]
+fn categorical_nb() {
+    #[derive(Clone, Copy)]
+    enum Furnishing {
+        Furnished,
+        SemiFurnished,
+        Unfurnished,
+    }
+
+    struct House {
+        furnishing: Furnishing,
+        stories: u8,
+    }
+
+    impl Estimatable for House {
+        fn prepare_for_estimation<F: num_traits::Float>(&self) -> Array1<F> {
+            arr1(&[
+                F::from(self.furnishing as u8).unwrap(),
+                F::from(self.stories).unwrap(),
+            ])
+        }
+    }
+
+    let houses = [
+        (Furnishing::Furnished, 2, true),
+        (Furnishing::Furnished, 3, true),
+        (Furnishing::SemiFurnished, 2, true),
+        (Furnishing::Unfurnished, 1, false),
+        (Furnishing::Unfurnished, 1, false),
+        (Furnishing::SemiFurnished, 1, false),
+    ];
+
+    let dataset = ClassificationDataSet::from(houses.iter().map(|(furnishing, stories, label)| {
+        (
+            House {
+                furnishing: *furnishing,
+                stories: *stories,
+            },
+            *label,
+        )
+    }));
+
+    let model = CategoricalNBEstimator::default().fit(&dataset).unwrap();
+
+    let predictions = model
+        .predict(
+            [
+                House {
+                    furnishing: Furnishing::Furnished,
+                    stories: 3,
+                },
+                House {
+                    furnishing: Furnishing::Unfurnished,
+                    stories: 1,
+                },
+                // 4 stories never occurred during training
+                House {
+                    furnishing: Furnishing::Furnished,
+                    stories: 4,
+                },
+            ]
+            .into_iter(),
+        )
+        .unwrap();
+
+    assert_eq!(predictions, vec![true, false, true]);
+
+    let sparse: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+        (arr1(&[1e20]), true),
+        (arr1(&[1e20]), true),
+        (arr1(&[1e9]), false),
+        (arr1(&[7.]), false),
+    ]);
+
+    let model = CategoricalNBEstimator::default().fit(&sparse).unwrap();
+    let predictions = model
+        .predict([arr1(&[1e20]), arr1(&[1e9])].into_iter())
+        .unwrap();
+
+    assert_eq!(predictions, vec![true, false]);
+    assert!(model.predict([arr1(&[0.5])].into_iter()).is_none());
+    assert!(CategoricalNBEstimator::default()
+        .fit(&ClassificationDataSet::from(vec![(
+            arr1(&[f64::INFINITY]),
+            true
+        )]))
+        .is_none());
+}
+
+#[test]
+fn complement_nb() {
+    let arr = arr2(&[
+        [4., 0., 1.],
+        [5., 1., 0.],
+        [3., 0., 0.],
+        [6., 1., 1.],
+        [4., 1., 0.],
+        [5., 0., 1.],
+        [0., 4., 3.],
+    ]);
+
+    let labels = vec![
+        "majority", "majority", "majority", "majority", "majority", "majority", "minority",
+    ];
+
+    let dataset: ClassificationDataSet<Array1<f64>, &str> =
+        ClassificationDataSet::from(arr.rows().into_iter().map(|row| row.to_owned()).zip(labels));
+
+    for norm in [false, true] {
+        let model = ComplementNBEstimator::new(1.0, norm).fit(&dataset).unwrap();
+
+        assert_eq!(model.labels(), &["majority", "minority"]);
+
+        let predictions = model
+            .predict([arr1(&[5., 0., 0.]), arr1(&[0., 2., 2.])].into_iter())
+            .unwrap();
+
+        assert_eq!(predictions, vec!["majority", "minority"]);
+    }
+}
+
+#[test]
+fn gaussian_nb_partial_fit() {
+    let arr = arr2(&[
+        [0., 1., 2.],
+        [9., 77., 3.],
+        [3., 2., 10.],
+        [2., 2., 90.],
+        [8., 24., 100.],
+        [1., 3., 4.],
+        [7., 50., 80.],
+        [5., 5., 5.],
+    ]);
+
+    let labels = vec!["a", "b", "a", "b", "b", "a", "c", "c"];
+
+    let records: Vec<_> = arr
+        .rows()
+        .into_iter()
+        .map(|row| row.to_owned())
+        .zip(labels)
+        .collect();
+
+    let full = ClassificationDataSet::from(records.clone());
+    let first_batch = ClassificationDataSet::from(records[..5].to_vec());
+    let second_batch = ClassificationDataSet::from(records[5..].to_vec());
+
+    // smoothing depends on the variance of the first batch, so disable it to compare models.
+    let estimator = GaussianNBEstimator::new(0.);
+
+    let model = estimator.fit(&full).unwrap();
+
+    let mut incremental = estimator.fit(&first_batch).unwrap();
+    incremental.partial_fit(&second_batch).unwrap();
+
+    assert_eq!(incremental.labels(), model.labels());
+
+    let test = arr2(&[[1., 2., 3.], [6., 30., 70.], [4., 4., 6.]]);
+
+    let expected = model
+        .predict_proba(test.rows().into_iter().map(|row| row.to_owned()))
+        .unwrap();
+    let actual = incremental
+        .predict_proba(test.rows().into_iter().map(|row| row.to_owned()))
+        .unwrap();
+
+    assert!(actual.abs_diff_eq(&expected, 1e-8));
+
+    let wrong_shape = ClassificationDataSet::from(vec![(arr1(&[1., 2.]), "a")]);
+    assert!(incremental.partial_fit(&wrong_shape).is_none());
+}
+
+#[test]
+fn gaussian_nb_log_proba() {
+    // second feature is constant within each class
+    let arr = arr2(&[
+        [0., 1.],
+        [1., 1.],
+        [2., 1.],
+        [10., 5.],
+        [11., 5.],
+        [12., 5.],
+    ]);
+
+    let labels = vec![true, true, true, false, false, false];
+
+    let dataset: ClassificationDataSet<Array1<f64>, bool> =
+        ClassificationDataSet::from(arr.rows().into_iter().map(|row| row.to_owned()).zip(labels));
+
+    let model = GaussianNBEstimator::default().fit(&dataset).unwrap();
+
+    let far_away = [arr1(&[1e6, 1.]), arr1(&[-1e6, 5.])];
+
+    let log_proba = model
+        .predict_log_proba(far_away.clone().into_iter())
+        .unwrap();
+    let proba = model.predict_proba(far_away.clone().into_iter()).unwrap();
+
+    assert!(log_proba.iter().all(|v| !v.is_nan()));
+    assert!(proba
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+
+    let predictions = model.predict(far_away.into_iter()).unwrap();
+
+    assert_eq!(predictions, vec![true, false]);
+}
+
+#[test]
+fn gaussian_nb_priors_and_weights() {
+    let arr = arr2(&[[0., 0.], [1., 1.], [0., 1.], [4., 4.], [5., 5.], [4., 5.]]);
+
+    let labels = vec![false, false, false, true, true, true];
+
+    let dataset: ClassificationDataSet<Array1<f64>, bool> =
+        ClassificationDataSet::from(arr.rows().into_iter().map(|row| row.to_owned()).zip(labels));
+
+    let midpoint = [arr1(&[2.5, 2.5])];
+
+    let uniform = GaussianNBEstimator::default().fit(&dataset).unwrap();
+    let skewed = GaussianNBEstimator::default()
+        .with_priors(vec![(true, 0.999), (false, 0.001)])
+        .fit(&dataset)
+        .unwrap();
+
+    let uniform_proba = uniform.predict_proba(midpoint.clone().into_iter()).unwrap();
+    let skewed_proba = skewed.predict_proba(midpoint.clone().into_iter()).unwrap();
+
+    // column 1 holds the likelihood of `true`
+    assert!(skewed_proba[(0, 1)] > uniform_proba[(0, 1)]);
+
+    let missing_prior = GaussianNBEstimator::default().with_priors(vec![(true, 1.)]);
+    assert!(missing_prior.fit(&dataset).is_none());
+
+    let not_normalized = GaussianNBEstimator::default().with_priors(vec![(true, 1.), (false, 1.)]);
+    assert!(not_normalized.fit(&dataset).is_none());
+
+    // unit weights are the same as an unweighted fit
+    let unit = GaussianNBEstimator::default()
+        .fit_weighted(&dataset, &[1.; 6])
+        .unwrap();
+
+    assert!(unit
+        .predict_proba(midpoint.clone().into_iter())
+        .unwrap()
+        .abs_diff_eq(&uniform_proba, 1e-10));
+
+    // doubling a record is the same as weighting it twice
+    let duplicated: ClassificationDataSet<Array1<f64>, bool> = ClassificationDataSet::from(vec![
+        (arr1(&[0., 0.]), false),
+        (arr1(&[0., 0.]), false),
+        (arr1(&[1., 1.]), false),
+        (arr1(&[0., 1.]), false),
+        (arr1(&[4., 4.]), true),
+        (arr1(&[5., 5.]), true),
+        (arr1(&[4., 5.]), true),
+    ]);
+
+    let weighted = GaussianNBEstimator::new(0.)
+        .fit_weighted(&dataset, &[2., 1., 1., 1., 1., 1.])
+        .unwrap();
+    let duplicated = GaussianNBEstimator::new(0.).fit(&duplicated).unwrap();
+
+    assert!(weighted
+        .predict_proba(midpoint.clone().into_iter())
+        .unwrap()
+        .abs_diff_eq(
+            &duplicated.predict_proba(midpoint.into_iter()).unwrap(),
+            1e-10
+        ));
+
+    assert!(GaussianNBEstimator::default()
+        .fit_weighted(&dataset, &[1.; 5])
+        .is_none());
+}
+
+#[test]
+fn logistic_regression() {
+    let centers = [(0., 0.), (5., 0.), (0., 5.)];
+    let offsets = [(-0.5, -0.5), (0.5, 0.), (0., 0.5), (0.3, -0.3), (-0.2, 0.4)];
+
+    let records: Vec<(Array1<f64>, usize)> = centers
+        .iter()
+        .enumerate()
+        .flat_map(|(label, (x, y))| {
+            offsets
+                .iter()
+                .map(move |(dx, dy)| (arr1(&[x + dx, y + dy]), label))
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records.clone());
+
+    let model = LogisticRegressionEstimator::default()
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(model.coefficients().dim(), (3, 2));
+    assert_eq!(model.intercepts().len(), 3);
+
+    let predictions = model
+        .predict(records.iter().map(|(features, _)| features.clone()))
+        .unwrap();
+
+    let labels: Vec<usize> = records.iter().map(|(_, label)| *label).collect();
+    assert_eq!(predictions, labels);
+
+    let proba = model
+        .predict_proba([arr1(&[0., 0.]), arr1(&[5., 0.]), arr1(&[0., 5.])].into_iter())
+        .unwrap();
+
+    assert!(proba
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+    assert!(proba.diag().iter().all(|p| *p > 0.8));
+}
+
+#[test]
+fn logistic_regression_l1() {
+    // first feature separates the labels, second feature is noise
+    let records: Vec<(Array1<f64>, bool)> = (0..40)
+        .map(|i| {
+            let label = i % 2 == 0;
+            let informative = if label { 1. } else { -1. } + (i % 5) as f64 * 0.1;
+            let noise = ((i * 7) % 11) as f64 / 10. - 0.5;
+
+            (arr1(&[informative, noise]), label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records);
+
+    let model = LogisticRegressionEstimator::default()
+        .with_penalty(Penalty::L1(0.05))
+        .with_solver(Solver::Sgd {
+            learning_rate: 0.5,
+            batch_size: 4,
+            seed: Some(42),
+        })
+        .with_max_iter(200)
+        .fit(&dataset)
+        .unwrap();
+
+    let coefficients = model.coefficients();
+
+    // labels are discovered in order of appearance, so coefficients are for `false`
+    assert!(coefficients[(0, 0)] < 0.);
+    assert_eq!(coefficients[(0, 1)], 0.);
+
+    let lbfgs = LogisticRegressionEstimator::default().with_penalty(Penalty::L1(0.05));
+    assert!(lbfgs.fit(&dataset).is_none());
+}
+
+#[test]
+fn k_neighbors() {
+    let mut rng = StdRng::seed_from_u64(7);
+
+    let records: Vec<(Array1<f64>, usize)> = (0..200)
+        .map(|i| {
+            let label = i % 3;
+            let features = Array1::from_iter(
+                (0..3).map(|dim| (label * dim) as f64 + rng.random_range(-1.5..1.5)),
+            );
+
+            (features, label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records.clone());
+
+    let test: Vec<Array1<f64>> = (0..50)
+        .map(|_| Array1::from_iter((0..3).map(|_| rng.random_range(-2.0..6.0))))
+        .collect();
+
+    for metric in [Metric::Euclidean, Metric::Manhattan, Metric::Minkowski(3.)] {
+        for weights in [Weights::Uniform, Weights::Distance] {
+            let estimator = KNeighborsClassifierEstimator::new(7)
+                .with_metric(metric)
+                .with_weights(weights)
+                .with_leaf_size(4);
+
+            let kd_tree = estimator
+                .with_algorithm(Algorithm::KdTree)
+                .fit(&dataset)
+                .unwrap();
+            let brute_force = estimator
+                .with_algorithm(Algorithm::BruteForce)
+                .fit(&dataset)
+                .unwrap();
+
+            let expected = brute_force.predict_proba(test.iter().cloned()).unwrap();
+            let actual = kd_tree.predict_proba(test.iter().cloned()).unwrap();
+
+            assert!(actual.abs_diff_eq(&expected, 1e-10));
+            assert!(actual
+                .sum_axis(Axis(1))
+                .iter()
+                .all(|total| (total - 1.).abs() < 1e-10));
+        }
+    }
+
+    let model = KNeighborsClassifierEstimator::new(1)
+        .with_metric(Metric::Cosine)
+        .fit(&dataset)
+        .unwrap();
+
+    let predictions = model
+        .predict(records.iter().map(|(features, _)| features.clone()))
+        .unwrap();
+
+    assert!(predictions
+        .iter()
+        .zip(records.iter())
+        .all(|(prediction, (_, label))| prediction == label));
+
+    let cosine_kd_tree = KNeighborsClassifierEstimator::new(3)
+        .with_metric(Metric::Cosine)
+        .with_algorithm(Algorithm::KdTree);
+
+    assert!(cosine_kd_tree.fit(&dataset).is_none());
+
+    let parallel: ClassificationDataSet<Array1<f64>, &str> =
+        ClassificationDataSet::from(vec![(arr1(&[0.3, 0.7]), "a"), (arr1(&[-1., 0.2]), "b")]);
+
+    let model = KNeighborsClassifierEstimator::new(2)
+        .with_metric(Metric::Cosine)
+        .with_weights(Weights::Distance)
+        .fit(&parallel)
+        .unwrap();
+
+    let query = arr1(&[0.3, 0.7]) * 3.;
+
+    assert_eq!(
+        Metric::Cosine.distance(parallel.get_features()[0].view(), query.view()),
+        0.
+    );
+    assert_eq!(
+        model.predict_proba([query].into_iter()).unwrap(),
+        arr2(&[[1., 0.]])
+    );
+}
+
+#[test]
+fn decision_tree() {
+    let dataset = ClassificationDataSet::from(vec![
+        (arr1(&[1., 5., 0.]), "a"),
+        (arr1(&[2., 4., 1.]), "a"),
+        (arr1(&[3., 6., 0.]), "a"),
+        (arr1(&[4., 5., 1.]), "b"),
+        (arr1(&[5., 4., 0.]), "b"),
+        (arr1(&[6., 6., 1.]), "b"),
+        (arr1(&[7., 5., 1.]), "c"),
+        (arr1(&[8., 4., 1.]), "c"),
+    ]);
+
+    for criterion in [Criterion::Gini, Criterion::Entropy] {
+        let model = DecisionTreeClassifierEstimator::default()
+            .with_criterion(criterion)
+            .fit(&dataset)
+            .unwrap();
+
+        let predictions = model
+            .predict(dataset.get_features().into_iter().cloned())
+            .unwrap();
+
+        assert_eq!(
+            predictions,
+            dataset
+                .get_labels()
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+
+        let importances = model.feature_importances();
+
+        assert!((importances.sum() - 1.).abs() < 1e-10);
+        assert!(importances[0] > 0.99);
+        assert_eq!(model.leaf_count(), 3);
+        assert_eq!(model.depth(), 2);
+
+        let text = model.export_text(Some(&["x", "y", "z"]));
+
+        assert_eq!(text.lines().count(), 7);
+        assert!(text.starts_with("|--- x <= 3.5000"));
+    }
+
+    let stump = DecisionTreeClassifierEstimator::default()
+        .with_max_depth(Some(1))
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(stump.depth(), 1);
+    assert!(stump
+        .predict_proba([arr1(&[7.5, 5., 1.])].into_iter())
+        .unwrap()
+        .abs_diff_eq(&arr2(&[[0., 0.6, 0.4]]), 1e-10));
+
+    let leafy = DecisionTreeClassifierEstimator::default()
+        .with_min_samples_leaf(3)
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(leafy.leaf_count(), 2);
+
+    let random = DecisionTreeClassifierEstimator::default()
+        .with_max_features(MaxFeatures::Count(1))
+        .with_seed(Some(7));
+
+    let first = random.fit(&dataset).unwrap().export_text(None);
+    let second = random.fit(&dataset).unwrap().export_text(None);
+
+    assert_eq!(first, second);
+    assert!(DecisionTreeClassifierEstimator::default()
+        .with_min_samples_split(1)
+        .fit(&dataset)
+        .is_none());
+}
+
+#[test]
+fn random_forest() {
+    let mut rng = StdRng::seed_from_u64(11);
+
+    let records: Vec<(Array1<f64>, usize)> = (0..90)
+        .map(|idx| {
+            let label = idx % 3;
+            let features = Array1::from_iter([
+                label as f64 * 2. + rng.random_range(-1.5..1.5),
+                rng.random_range(-1.0..1.0),
+                rng.random_range(-1.0..1.0),
+            ]);
+
+            (features, label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records.clone());
+
+    let estimator = RandomForestClassifierEstimator::new(30)
+        .with_seed(Some(3))
+        .with_oob_score(true);
+
+    let model = estimator.fit(&dataset).unwrap();
+
+    assert_eq!(model.n_estimators(), 30);
+
+    let proba = model
+        .predict_proba(records.iter().map(|(features, _)| features.clone()))
+        .unwrap();
+
+    assert!(proba
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+
+    let importances = model.feature_importances();
+
+    assert!((importances.sum() - 1.).abs() < 1e-10);
+    assert!(importances[0] > importances[1] && importances[0] > importances[2]);
+    assert!(model.oob_score().unwrap() > 0.7);
+
+    let again = estimator.fit(&dataset).unwrap();
+
+    assert_eq!(
+        again.predict_proba(records.iter().map(|(features, _)| features.clone())),
+        Some(proba)
+    );
+
+    let unbagged = RandomForestClassifierEstimator::new(5)
+        .with_bootstrap(false)
+        .with_max_features(MaxFeatures::All)
+        .fit(&dataset)
+        .unwrap();
+
+    assert!(unbagged.oob_score().is_none());
+    assert!(RandomForestClassifierEstimator::new(5)
+        .with_bootstrap(false)
+        .with_oob_score(true)
+        .fit(&dataset)
+        .is_none());
+    assert!(RandomForestClassifierEstimator::new(0)
+        .fit(&dataset)
+        .is_none());
+}
+
+#[test]
+fn gradient_boosting() {
+    let mut rng = StdRng::seed_from_u64(5);
+
+    let xor: Vec<(Array1<f64>, bool)> = (0..200)
+        .map(|_| {
+            let features = Array1::from_iter((0..2).map(|_| rng.random_range(-1.0..1.0)));
+            let label = (features[0] > 0.) != (features[1] > 0.);
+
+            (features, label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(xor.clone());
+
+    let model = GradientBoostingClassifierEstimator::new(50)
+        .with_max_depth(Some(2))
+        .with_subsample(0.8)
+        .with_seed(Some(1))
+        .fit(&dataset)
+        .unwrap();
+
+    let truth: Vec<bool> = xor.iter().map(|(_, label)| *label).collect();
+    let predictions = model
+        .predict(xor.iter().map(|(features, _)| features.clone()))
+        .unwrap();
+
+    assert!(accuracy(truth, predictions).unwrap() > 0.95);
+
+    let scores = model
+        .decision_function([arr1(&[0.5, 0.5]), arr1(&[-0.5, 0.5])].into_iter())
+        .unwrap();
+
+    assert_eq!(scores.ncols(), 1);
+
+    let positive = model.labels().iter().position(|label| *label).unwrap();
+    let sign = match positive {
+        1 => 1.,
+        _ => -1.,
+    };
+
+    assert!(sign * scores[(0, 0)] < 0. && sign * scores[(1, 0)] > 0.);
+
+    let blobs: Vec<(Array1<f64>, usize)> = (0..150)
+        .map(|idx| {
+            let label = idx % 3;
+            let features = Array1::from_iter(
+                (0..2).map(|dim| (label * (dim + 1)) as f64 * 2. + rng.random_range(-1.0..1.0)),
+            );
+
+            (features, label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(blobs.clone());
+
+    let full = GradientBoostingClassifierEstimator::new(200)
+        .with_seed(Some(2))
+        .fit(&dataset)
+        .unwrap();
+    let early = GradientBoostingClassifierEstimator::new(200)
+        .with_n_iter_no_change(Some(3))
+        .with_validation_fraction(0.2)
+        .with_seed(Some(2))
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(full.n_estimators(), 200);
+    assert!(early.n_estimators() < 200);
+
+    let proba = early
+        .predict_proba(blobs.iter().map(|(features, _)| features.clone()))
+        .unwrap();
+
+    assert_eq!(proba.ncols(), 3);
+    assert!(proba
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+
+    let single = ClassificationDataSet::from(vec![(arr1(&[1.]), 0), (arr1(&[2.]), 0)]);
+
+    assert!(GradientBoostingClassifierEstimator::default()
+        .fit(&single)
+        .is_none());
+    assert!(GradientBoostingClassifierEstimator::default()
+        .with_subsample(1.5)
+        .fit(&dataset)
+        .is_none());
+}
+
+#[test]
+fn linear_svc() {
+    let mut rng = StdRng::seed_from_u64(13);
+
+    let records: Vec<(Array1<f64>, &str)> = (0..120)
+        .map(|idx| {
+            let (label, center) = match idx % 3 {
+                0 => ("left", [-3., 0.]),
+                1 => ("right", [3., 0.]),
+                _ => ("up", [0., 4.]),
+            };
+            let features = Array1::from_iter(
+                center
+                    .iter()
+                    .map(|coordinate| coordinate + rng.random_range(-1.0..1.0)),
+            );
+
+            (features, label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records.clone());
+
+    for loss in [Loss::Hinge, Loss::SquaredHinge] {
+        let model = LinearSVCEstimator::new(1.)
+            .with_loss(loss)
+            .with_seed(Some(0))
+            .fit(&dataset)
+            .unwrap();
+
+        assert_eq!(model.coefficients().dim(), (3, 2));
+
+        let predictions = model
+            .predict(records.iter().map(|(features, _)| features.clone()))
+            .unwrap();
+
+        assert!(predictions
+            .iter()
+            .zip(records.iter())
+            .all(|(prediction, (_, label))| prediction == label));
+
+        let proba = model
+            .predict_proba(records.iter().map(|(features, _)| features.clone()))
+            .unwrap();
+
+        assert!(proba
+            .sum_axis(Axis(1))
+            .iter()
+            .all(|total| (total - 1.).abs() < 1e-10));
+
+        // undefined scores have no calibrated probabilities to normalize
+        let undefined = model
+            .predict_proba([arr1(&[f64::NAN, 0.])].into_iter())
+            .unwrap();
+
+        assert!(undefined.abs_diff_eq(&Array2::from_elem((1, 3), 1. / 3.), 1e-10));
+    }
+
+    let binary = ClassificationDataSet::from(
+        records
+            .iter()
+            .filter(|(_, label)| *label != "up")
+            .cloned()
+            .collect::<Vec<_>>(),
+    );
+
+    let model = LinearSVCEstimator::default()
+        .with_seed(Some(0))
+        .fit(&binary)
+        .unwrap();
+
+    let scores = model
+        .decision_function([arr1(&[-3., 0.]), arr1(&[3., 0.])].into_iter())
+        .unwrap();
+
+    assert_eq!(scores.ncols(), 1);
+    assert!(scores[(0, 0)] < 0. && scores[(1, 0)] > 0.);
+
+    let proba = model
+        .predict_proba([arr1(&[-3., 0.]), arr1(&[3., 0.])].into_iter())
+        .unwrap();
+
+    assert!(proba[(0, 0)] > 0.9 && proba[(1, 1)] > 0.9);
+    assert!(LinearSVCEstimator::new(0.).fit(&binary).is_none());
+}
+
+#[test]
+fn svc() {
+    let mut rng = StdRng::seed_from_u64(17);
+
+    let ring: Vec<(Array1<f64>, bool)> = (0..120)
+        .map(|idx| {
+            let inside = idx % 2 == 0;
+            let radius = match inside {
+                true => rng.random_range(0.0..1.0),
+                false => rng.random_range(2.0..3.0),
+            };
+            let angle: f64 = rng.random_range(0.0..std::f64::consts::TAU);
+
+            (arr1(&[radius * angle.cos(), radius * angle.sin()]), inside)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(ring.clone());
+    let truth: Vec<bool> = ring.iter().map(|(_, label)| *label).collect();
+    let c = 10.;
+
+    for kernel in [
+        Kernel::Rbf { gamma: None },
+        Kernel::Polynomial {
+            degree: 2,
+            gamma: Some(1.),
+            coef0: 1.,
+        },
+    ] {
+        let model = SVCEstimator::new(c)
+            .with_kernel(kernel)
+            .fit(&dataset)
+            .unwrap();
+
+        let predictions = model
+            .predict(ring.iter().map(|(features, _)| features.clone()))
+            .unwrap();
+
+        assert_eq!(accuracy(truth.clone(), predictions), Some(1.));
+        assert!(model.support_vectors().nrows() < ring.len());
+
+        // records violating the margin must be support vectors at the upper bound
+        let scores = model
+            .decision_function(ring.iter().map(|(features, _)| features.clone()))
+            .unwrap();
+        let sign = match model.labels()[1] {
+            true => 1.,
+            false => -1.,
+        };
+
+        for ((features, label), score) in ring.iter().zip(scores.column(0)) {
+            let margin = match label {
+                true => sign * score,
+                false => -sign * score,
+            };
+
+            if margin < 1. - 1e-2 {
+                let position = model
+                    .support_vectors()
+                    .rows()
+                    .into_iter()
+                    .position(|row| row == features.view())
+                    .unwrap();
+
+                assert!((model.dual_coefficients()[(0, position)].abs() - c).abs() < 1e-8);
+            }
+        }
+
+        let small_cache = SVCEstimator::new(c)
+            .with_kernel(kernel)
+            .with_cache_size(1)
+            .fit(&dataset)
+            .unwrap();
+
+        assert!(small_cache
+            .dual_coefficients()
+            .abs_diff_eq(model.dual_coefficients(), 1e-10));
+    }
+
+    let linear = SVCEstimator::new(1.)
+        .with_kernel(Kernel::Linear)
+        .fit(&dataset)
+        .unwrap();
+    let predictions = linear
+        .predict(ring.iter().map(|(features, _)| features.clone()))
+        .unwrap();
+
+    assert!(accuracy(truth, predictions).unwrap() < 0.9);
+
+    let blobs: Vec<(Array1<f64>, usize)> = (0..90)
+        .map(|idx| {
+            let label = idx % 3;
+            let features = Array1::from_iter(
+                (0..2).map(|dim| (label * (dim + 1)) as f64 * 2. + rng.random_range(-1.0..1.0)),
+            );
+
+            (features, label)
+        })
+        .collect();
+
+    let model = SVCEstimator::default()
+        .with_kernel(Kernel::Sigmoid {
+            gamma: Some(0.01),
+            coef0: 0.,
+        })
+        .fit(&ClassificationDataSet::from(blobs.clone()))
+        .unwrap();
+
+    let proba = model
+        .predict_proba(blobs.iter().map(|(features, _)| features.clone()))
+        .unwrap();
+
+    assert_eq!(model.dual_coefficients().nrows(), 3);
+    assert!(proba
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+    assert!(SVCEstimator::default()
+        .with_kernel(Kernel::Rbf { gamma: Some(0.) })
+        .fit(&dataset)
+        .is_none());
+
+    // random labels with a tolerance below rounding error never converge, so fitting relies on
+    // the default iteration cap to return
+    let noise = ClassificationDataSet::from(
+        (0..30)
+            .map(|idx| {
+                (
+                    arr1(&[rng.random_range(0.0..1.0), (idx % 3) as f64]),
+                    rng.random_bool(0.5),
+                )
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    assert!(SVCEstimator::new(1e6)
+        .with_kernel(Kernel::Rbf { gamma: Some(10.) })
+        .with_tol(1e-300)
+        .fit(&noise)
+        .is_some());
+}
+
+#[test]
+fn discriminant_analysis() {
+    let mut rng = StdRng::seed_from_u64(19);
+
+    // elongated, correlated clusters whose centroids are closer across labels than within
+    let records: Vec<(Array1<f64>, &str)> = (0..200)
+        .map(|idx| {
+            let along: f64 = rng.random_range(-4.0..4.0);
+            let across: f64 = rng.random_range(-0.3..0.3);
+            let label = match idx % 2 {
+                0 => "above",
+                _ => "below",
+            };
+            let offset = match label {
+                "above" => 0.6,
+                _ => -0.6,
+            };
+
+            (arr1(&[along, along + across + offset]), label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records.clone());
+    let truth: Vec<&str> = records.iter().map(|(_, label)| *label).collect();
+
+    let lda = LinearDiscriminantAnalysisEstimator::default()
+        .fit(&dataset)
+        .unwrap();
+
+    let predictions = lda
+        .predict(records.iter().map(|(features, _)| features.clone()))
+        .unwrap();
+
+    assert!(accuracy(truth.clone(), predictions).unwrap() > 0.99);
+    assert!(lda
+        .covariance()
+        .dot(&lda.coefficients().t())
+        .abs_diff_eq(&lda.means().t(), 1e-8));
+
+    let shrunk = LinearDiscriminantAnalysisEstimator::default()
+        .with_shrinkage(Some(1.))
+        .fit(&dataset)
+        .unwrap();
+
+    assert!(shrunk.covariance()[(0, 1)].abs() < 1e-12);
+    assert!(LinearDiscriminantAnalysisEstimator::default()
+        .with_shrinkage(Some(1.5))
+        .fit(&dataset)
+        .is_none());
+
+    let qda = QuadraticDiscriminantAnalysisEstimator::default()
+        .fit(&dataset)
+        .unwrap();
+
+    let covariances = qda.covariances();
+    let above: Vec<Array1<f64>> = records
+        .iter()
+        .filter(|(_, label)| *label == "above")
+        .map(|(features, _)| features.clone())
+        .collect();
+    let above = Array2::from_shape_fn((above.len(), 2), |(i, j)| above[i][j]);
+    let centered = &above - &above.mean_axis(Axis(0)).unwrap();
+    let expected = centered.t().dot(&centered) / (above.nrows() - 1) as f64;
+
+    assert!(covariances
+        .index_axis(Axis(0), 0)
+        .abs_diff_eq(&expected, 1e-8));
+
+    let rings: Vec<(Array1<f64>, bool)> = (0..200)
+        .map(|idx| {
+            let inside = idx % 2 == 0;
+            let scale = match inside {
+                true => 0.5,
+                false => 4.,
+            };
+
+            (
+                Array1::from_iter((0..2).map(|_| scale * rng.random_range(-1.0..1.0))),
+                inside,
+            )
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(rings.clone());
+    let truth: Vec<bool> = rings.iter().map(|(_, label)| *label).collect();
+
+    let qda = QuadraticDiscriminantAnalysisEstimator::default()
+        .fit(&dataset)
+        .unwrap();
+    let lda = LinearDiscriminantAnalysisEstimator::default()
+        .fit(&dataset)
+        .unwrap();
+
+    let qda_accuracy = accuracy(
+        truth.clone(),
+        qda.predict(rings.iter().map(|(features, _)| features.clone()))
+            .unwrap(),
+    )
+    .unwrap();
+    let lda_accuracy = accuracy(
+        truth,
+        lda.predict(rings.iter().map(|(features, _)| features.clone()))
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert!(qda_accuracy > 0.9);
+    assert!(lda_accuracy < 0.75);
+
+    let singleton = ClassificationDataSet::from(vec![
+        (arr1(&[0., 0.]), 0),
+        (arr1(&[1., 0.]), 0),
+        (arr1(&[0., 1.]), 0),
+        (arr1(&[5., 5.]), 1),
+    ]);
+
+    assert!(QuadraticDiscriminantAnalysisEstimator::default()
+        .fit(&singleton)
+        .is_none());
+    assert!(QuadraticDiscriminantAnalysisEstimator::default()
+        .with_reg_param(0.5)
+        .fit(&singleton)
+        .is_some());
+}
+
+#[test]
+fn mlp_classifier() {
+    let mut rng = StdRng::seed_from_u64(23);
+
+    let spiral: Vec<(Array1<f64>, usize)> = (0..240)
+        .map(|idx| {
+            let label = idx % 3;
+            let t = rng.random_range(0.2..1.0);
+            let angle = 4. * t + label as f64 * 2. * std::f64::consts::PI / 3.;
+
+            (arr1(&[t * angle.cos(), t * angle.sin()]), label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(spiral.clone());
+    let truth: Vec<usize> = spiral.iter().map(|(_, label)| *label).collect();
+
+    for activation in [Activation::Relu, Activation::Tanh] {
+        let model = MLPClassifierEstimator::new(vec![32, 16])
+            .with_activation(activation)
+            .with_optimizer(Optimizer::Adam {
+                learning_rate: 0.01,
+                beta_1: 0.9,
+                beta_2: 0.999,
+                epsilon: 1e-8,
+            })
+            .with_batch_size(Some(32))
+            .with_max_iter(300)
+            .with_seed(Some(4))
+            .fit(&dataset)
+            .unwrap();
+
+        let predictions = model
+            .predict(spiral.iter().map(|(features, _)| features.clone()))
+            .unwrap();
+
+        assert!(accuracy(truth.clone(), predictions).unwrap() > 0.95);
+        assert!(model.loss_curve().first() > model.loss_curve().last());
+        assert_eq!(model.coefficients().len(), 3);
+        assert_eq!(model.coefficients()[0].dim(), (2, 32));
+    }
+
+    let estimator = MLPClassifierEstimator::new(vec![16])
+        .with_activation(Activation::Logistic)
+        .with_optimizer(Optimizer::Sgd {
+            learning_rate: 0.1,
+            momentum: 0.9,
+        })
+        .with_alpha(1e-3)
+        .with_early_stopping(true)
+        .with_validation_fraction(0.2)
+        .with_max_iter(50)
+        .with_seed(Some(5));
+
+    let model = estimator.fit(&dataset).unwrap();
+    let again = estimator.fit(&dataset).unwrap();
+
+    assert!(model.n_iter() <= 50);
+    assert_eq!(model.loss_curve(), again.loss_curve());
+
+    let proba = model
+        .predict_proba(spiral.iter().map(|(features, _)| features.clone()))
+        .unwrap();
+
+    assert!(proba
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+    assert!(MLPClassifierEstimator::new(vec![0]).fit(&dataset).is_none());
+}
+
+#[test]
+fn multiclass_wrappers() {
+    let mut rng = StdRng::seed_from_u64(23);
+    let centers = [
+        ("left", -4., 0.),
+        ("right", 4., 0.),
+        ("up", 0., 4.),
+        ("down", 0., -4.),
+    ];
+
+    let records: Vec<(Array1<f64>, &str)> = (0..200)
+        .map(|idx| {
+            let (label, x, y) = centers[idx % centers.len()];
+
+            (
+                arr1(&[
+                    x + rng.random_range(-1.0..1.0),
+                    y + rng.random_range(-1.0..1.0),
+                ]),
+                label,
+            )
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records.clone());
+    let truth: Vec<&str> = records.iter().map(|(_, label)| *label).collect();
+    let features = || records.iter().map(|(features, _)| features.clone());
+
+    let ovr = OneVsRestEstimator::new(LogisticRegressionEstimator::default())
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(ovr.labels(), &["left", "right", "up", "down"]);
+    assert_eq!(ovr.classifiers().len(), 4);
+    assert!(accuracy(truth.clone(), ovr.predict(features()).unwrap()).unwrap() > 0.98);
+
+    let ovo = OneVsOneEstimator::new(LinearSVCEstimator::default().with_seed(Some(0)))
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(ovo.classifiers().len(), 6);
+    assert_eq!(ovo.pairs()[0], (0, 1));
+    assert!(accuracy(truth.clone(), ovo.predict(features()).unwrap()).unwrap() > 0.98);
+
+    let ovo_estimator = OneVsOneEstimator::new(LinearSVCEstimator::default().with_seed(Some(0)));
+    let single = ClassificationDataSet::from(
+        records
+            .iter()
+            .filter(|(_, label)| *label == "left")
+            .cloned(),
+    );
+    let empty = ClassificationDataSet::from(Vec::<(Array1<f64>, &str)>::new());
+
+    assert!(ovo_estimator.fit(&single).is_none());
+    assert!(ovo_estimator.fit(&empty).is_none());
+
+    for proba in [
+        ovr.predict_proba(features()).unwrap(),
+        ovo.predict_proba(features()).unwrap(),
+    ] {
+        assert_eq!(proba.dim(), (200, 4));
+        assert!(proba
+            .sum_axis(Axis(1))
+            .iter()
+            .all(|total| (total - 1.).abs() < 1e-10));
+    }
+
+    // binary problems are handled by a single classifier
+    let binary = ClassificationDataSet::from(
+        records
+            .iter()
+            .filter(|(_, label)| *label == "left" || *label == "right")
+            .cloned(),
+    );
+
+    let ovr = OneVsRestEstimator::new(GaussianNBEstimator::default())
+        .fit(&binary)
+        .unwrap();
+
+    assert_eq!(ovr.classifiers().len(), 1);
+    assert_eq!(
+        ovr.predict([arr1(&[-4., 0.]), arr1(&[4., 0.])].into_iter())
+            .unwrap(),
+        vec!["left", "right"]
+    );
+}
+
+#[test]
+fn calibrated_classifier() {
+    let mut rng = StdRng::seed_from_u64(29);
+
+    // a noisy signal repeated over several features makes naive Bayes count it several times
+    let mut draw = |n: usize| -> Vec<(Array1<f64>, bool)> {
+        (0..n)
+            .map(|_| {
+                let label = rng.random_bool(0.5);
+                let center = match label {
+                    true => 0.5,
+                    false => -0.5,
+                };
+                let signal: f64 = center + rng.random_range(-1.5..1.5);
+
+                (
+                    Array1::from_iter((0..5).map(|_| signal + rng.random_range(-0.1..0.1))),
+                    label,
+                )
+            })
+            .collect()
+    };
+
+    let train = draw(600);
+    let test = draw(600);
+
+    let dataset = ClassificationDataSet::from(train);
+    let features = || test.iter().map(|(features, _)| features.clone());
+
+    let brier = |model: &dyn Fn() -> Array2<f64>, labels: &[bool]| -> f64 {
+        let proba = model();
+        let positive = labels.iter().position(|label| *label).unwrap();
+
+        test.iter()
+            .zip(proba.column(positive))
+            .map(|((_, label), p)| (p - if *label { 1. } else { 0. }).powi(2))
+            .sum::<f64>()
+            / test.len() as f64
+    };
+
+    let raw = GaussianNBEstimator::default().fit(&dataset).unwrap();
+    let raw_brier = brier(&|| raw.predict_proba(features()).unwrap(), raw.labels());
+
+    for method in [Method::Sigmoid, Method::Isotonic] {
+        let calibrated = CalibratedClassifierEstimator::new(GaussianNBEstimator::default())
+            .with_method(method)
+            .with_seed(Some(1))
+            .fit(&dataset)
+            .unwrap();
+
+        let calibrated_brier = brier(
+            &|| calibrated.predict_proba(features()).unwrap(),
+            calibrated.labels(),
+        );
+
+        assert_eq!(calibrated.classifiers().count(), 5);
+        assert!(calibrated_brier < raw_brier);
+    }
+
+    let records: Vec<(Array1<f64>, usize)> = (0..90)
+        .map(|idx| {
+            (
+                arr1(&[(idx % 3) as f64 + rng.random_range(-0.8..0.8)]),
+                idx % 3,
+            )
+        })
+        .collect();
+    let dataset = ClassificationDataSet::from(records.clone());
+
+    let model = CalibratedClassifierEstimator::new(GaussianNBEstimator::default())
+        .with_method(Method::Isotonic)
+        .with_cv(3)
+        .with_seed(Some(2))
+        .fit(&dataset)
+        .unwrap();
+
+    let proba = model
+        .predict_proba(records.iter().map(|(features, _)| features.clone()))
+        .unwrap();
+
+    assert_eq!(proba.dim(), (90, 3));
+    assert!(proba
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+
+    let estimator = CalibratedClassifierEstimator::new(GaussianNBEstimator::default());
+
+    assert!(estimator.clone().with_cv(1).fit(&dataset).is_none());
+    assert!(estimator.with_cv(31).fit(&dataset).is_none());
+}
+
+#[test]
+fn voting_and_stacking() {
+    // member fitting a gaussian naive bayes classifier, reporting its labels in reverse order
+    #[derive(Debug, Default)]
+    struct ReversedNBEstimator(GaussianNBEstimator<&'static str>);
+
+    #[derive(Debug)]
+    struct ReversedNB(GaussianNB<Array1<f64>, &'static str>, Vec<&'static str>);
+
+    impl Estimator<ClassificationDataSet<Array1<f64>, &'static str>> for ReversedNBEstimator {
+        type Estimator = ReversedNB;
+
+        fn fit(
+            &self,
+            input: &ClassificationDataSet<Array1<f64>, &'static str>,
+        ) -> Option<ReversedNB> {
+            let model = self.0.fit(input)?;
+            let labels = model.labels().iter().rev().cloned().collect();
+
+            Some(ReversedNB(model, labels))
+        }
+    }
+
+    impl Classifier<Array1<f64>, &'static str> for ReversedNB {
+        fn labels(&self) -> &[&'static str] {
+            &self.1
+        }
+
+        fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+        where
+            I: Iterator<Item = Array1<f64>>,
+        {
+            let proba = self.0.predict_proba(arr)?;
+
+            Some(proba.slice(ndarray::s![.., ..;-1]).to_owned())
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(31);
+    let centers = [("left", -2., 0.), ("right", 2., 0.), ("up", 0., 2.)];
+
+    let records: Vec<(Array1<f64>, &'static str)> = (0..150)
+        .map(|idx| {
+            let (label, x, y) = centers[idx % centers.len()];
+
+            (
+                arr1(&[
+                    x + rng.random_range(-1.5..1.5),
+                    y + rng.random_range(-1.5..1.5),
+                ]),
+                label,
+            )
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records.clone());
+    let truth: Vec<&str> = records.iter().map(|(_, label)| *label).collect();
+    let features = || records.iter().map(|(features, _)| features.clone());
+
+    let nb_proba = GaussianNBEstimator::default()
+        .fit(&dataset)
+        .unwrap()
+        .predict_proba(features())
+        .unwrap();
+
+    let reversed = VotingClassifierEstimator::new()
+        .with_estimator(ReversedNBEstimator::default())
+        .with_voting(Voting::Soft)
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(reversed.classifiers()[0].labels(), &["up", "right", "left"]);
+    assert_eq!(reversed.labels(), &["left", "right", "up"]);
+    assert!(reversed
+        .predict_proba(features())
+        .unwrap()
+        .abs_diff_eq(&nb_proba, 1e-12));
+
+    for voting in [Voting::Hard, Voting::Soft] {
+        let model = VotingClassifierEstimator::new()
+            .with_estimator(ReversedNBEstimator::default())
+            .with_estimator(LogisticRegressionEstimator::default())
+            .with_estimator(DecisionTreeClassifierEstimator::default().with_max_depth(Some(3)))
+            .with_weights(Some(vec![1., 1., 0.5]))
+            .with_voting(voting)
+            .fit(&dataset)
+            .unwrap();
+
+        let proba = model.predict_proba(features()).unwrap();
+
+        assert_eq!(model.weights(), &[1., 1., 0.5]);
+        assert!(proba
+            .sum_axis(Axis(1))
+            .iter()
+            .all(|total| (total - 1.).abs() < 1e-10));
+        assert!(accuracy(truth.clone(), model.predict(features()).unwrap()).unwrap() > 0.9);
+    }
+
+    let estimator = VotingClassifierEstimator::new()
+        .with_estimator(GaussianNBEstimator::default())
+        .with_weights(Some(vec![1., 1.]));
+
+    assert!(estimator.fit(&dataset).is_none());
+    assert!(VotingClassifierEstimator::<Array1<f64>, &str>::new()
+        .fit(&dataset)
+        .is_none());
+
+    let model = StackingClassifierEstimator::new(LogisticRegressionEstimator::default())
+        .with_estimator(ReversedNBEstimator::default())
+        .with_estimator(KNeighborsClassifierEstimator::new(5))
+        .with_seed(Some(3))
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(model.classifiers().len(), 2);
+    assert_eq!(model.transform(features()).unwrap().dim(), (150, 6));
+    assert_eq!(model.final_classifier().labels(), model.labels());
+    assert!(accuracy(truth.clone(), model.predict(features()).unwrap()).unwrap() > 0.9);
+}
+
+#[test]
+fn classifier_provided_methods() {
+    let records: Vec<(Array1<f64>, &str)> = (0..40)
+        .map(|idx| {
+            let x = (idx % 20) as f64 / 4.;
+            let label = match (idx % 4 == 1) ^ (x > 2.5) {
+                true => "spam",
+                false => "ham",
+            };
+
+            (arr1(&[x]), label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records.clone());
+    let model = LogisticRegressionEstimator::default()
+        .fit(&dataset)
+        .unwrap();
+    let features = || records.iter().map(|(features, _)| features.clone());
+
+    let proba = model.predict_proba(features()).unwrap();
+
+    assert_eq!(model.labels(), &["ham", "spam"]);
+    assert_eq!(
+        model
+            .predict_with_threshold(features(), &"spam", 0.5)
+            .unwrap(),
+        model.predict(features()).unwrap()
+    );
+    assert!(model
+        .predict_with_threshold(features(), &"spam", 0.)
+        .unwrap()
+        .iter()
+        .all(|label| *label == "spam"));
+
+    let strict = model
+        .predict_with_threshold(features(), &"spam", 0.8)
+        .unwrap();
+
+    for (label, p) in strict.iter().zip(proba.column(1)) {
+        assert_eq!(*label == "spam", *p >= 0.8);
+    }
+
+    let lenient = model
+        .predict_with_threshold(features(), &"ham", 0.2)
+        .unwrap();
+
+    for (label, p) in lenient.iter().zip(proba.column(0)) {
+        assert_eq!(*label == "ham", *p >= 0.2);
+    }
+
+    assert!(model
+        .predict_with_threshold(features(), &"eggs", 0.5)
+        .is_none());
+
+    // the first record is positive, so `true` is the first label of the model
+    let flagged = ClassificationDataSet::from(
+        records
+            .iter()
+            .rev()
+            .map(|(features, label)| (features.clone(), *label == "spam")),
+    );
+    let flagged_model = LogisticRegressionEstimator::default()
+        .fit(&flagged)
+        .unwrap();
+    let flagged_proba = flagged_model.predict_proba(features()).unwrap();
+
+    assert_eq!(flagged_model.labels(), &[true, false]);
+
+    for (label, p) in flagged_model
+        .predict_with_threshold(features(), &true, 0.8)
+        .unwrap()
+        .iter()
+        .zip(flagged_proba.column(0))
+    {
+        assert_eq!(*label, *p >= 0.8);
+    }
+
+    // missing spam costs a fifth of flagging ham, so spam is predicted from p >= 5 / 6
+    let costs = arr2(&[[0., 5.], [1., 0.]]);
+
+    for (label, p) in model
+        .predict_with_costs(features(), &costs)
+        .unwrap()
+        .iter()
+        .zip(proba.column(1))
+    {
+        assert_eq!(*label == "spam", *p > 5. / 6.);
+    }
+
+    assert!(model
+        .predict_with_costs(features(), &Array2::zeros((3, 3)))
+        .is_none());
+
+    let top_k = model.predict_top_k(features(), 1).unwrap();
+
+    assert_eq!(
+        top_k
+            .iter()
+            .map(|ranked| ranked[0].0)
+            .collect::<Vec<&str>>(),
+        model.predict(features()).unwrap()
+    );
+    assert_eq!(model.predict_top_k(features(), 5).unwrap()[0].len(), 2);
+
+    let records: Vec<(Array1<f64>, usize)> = (0..30)
+        .map(|idx| (arr1(&[(idx % 3) as f64 + (idx as f64 / 100.)]), idx % 3))
+        .collect();
+    let dataset = ClassificationDataSet::from(records.clone());
+    let model = GaussianNBEstimator::default().fit(&dataset).unwrap();
+
+    for ranked in model
+        .predict_top_k(records.iter().map(|(features, _)| features.clone()), 3)
+        .unwrap()
+    {
+        assert_eq!(ranked.len(), 3);
+        assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+
+    assert!(model
+        .predict_with_threshold(
+            records.iter().map(|(features, _)| features.clone()),
+            &records[0].1,
+            0.5
+        )
+        .is_none());
+
+    #[derive(Debug)]
+    struct Unfitted;
+
+    impl Classifier<Array1<f64>, usize> for Unfitted {
+        fn labels(&self) -> &[usize] {
+            &[]
+        }
+
+        fn predict_proba<I>(&self, arr: I) -> Option<Array2<f64>>
+        where
+            I: Iterator<Item = Array1<f64>>,
+        {
+            Some(Array2::zeros((arr.count(), 0)))
+        }
+    }
+
+    assert!(Unfitted.predict([arr1(&[0.])].into_iter()).is_none());
+    assert_eq!(Unfitted.predict(std::iter::empty()), Some(vec![]));
+}
+
+#[test]
+fn dummy_classifier() {
+    let records: Vec<(Array1<f64>, &str)> = (0..100)
+        .map(|idx| {
+            let label = match idx % 4 {
+                0 => "rare",
+                _ => "common",
+            };
+
+            (arr1(&[idx as f64]), label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records.clone());
+    let truth: Vec<&str> = records.iter().map(|(_, label)| *label).collect();
+    let features = || records.iter().map(|(features, _)| features.clone());
+
+    let model = DummyClassifierEstimator::default().fit(&dataset).unwrap();
+
+    assert_eq!(model.labels(), &["rare", "common"]);
+    assert_eq!(model.priors(), &arr1(&[0.25, 0.75]));
+    assert_eq!(
+        accuracy(truth.clone(), model.predict(features()).unwrap()),
+        Some(0.75)
+    );
+    assert!(model
+        .predict_proba(features())
+        .unwrap()
+        .rows()
+        .into_iter()
+        .all(|row| row == arr1(&[0., 1.])));
+
+    let estimator = DummyClassifierEstimator::new(Strategy::Stratified).with_seed(Some(5));
+    let model = estimator.fit(&dataset).unwrap();
+    let predictions = model.predict(features()).unwrap();
+
+    assert_eq!(
+        predictions,
+        estimator
+            .fit(&dataset)
+            .unwrap()
+            .predict(features())
+            .unwrap()
+    );
+    assert_eq!(
+        predictions,
+        model
+            .predict_proba(features())
+            .unwrap()
+            .rows()
+            .into_iter()
+            .map(|row| match row[0] == 1. {
+                true => "rare",
+                false => "common",
+            })
+            .collect::<Vec<&str>>()
+    );
+    assert!((20..=30).contains(&predictions.iter().filter(|label| **label == "rare").count()));
+
+    let model = DummyClassifierEstimator::new(Strategy::Uniform)
+        .with_seed(Some(6))
+        .fit(&dataset)
+        .unwrap();
+    let predictions = model.predict(features()).unwrap();
+
+    assert!(model
+        .predict_proba(features())
+        .unwrap()
+        .iter()
+        .all(|p| *p == 0.5));
+    assert!((35..=65).contains(&predictions.iter().filter(|label| **label == "rare").count()));
+
+    let model = DummyClassifierEstimator::new(Strategy::Constant("rare"))
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(
+        accuracy(truth, model.predict(features()).unwrap()),
+        Some(0.25)
+    );
+    assert!(DummyClassifierEstimator::new(Strategy::Constant("missing"))
+        .fit(&dataset)
+        .is_none());
+}
+
+#[test]
+fn dummy_regressor() {
+    let x = arr2(&[[0.], [1.], [2.], [3.], [4.]]);
+    let y = arr1(&[4., 1., 3., 2., 100.]);
+
+    for (strategy, constant) in [
+        (RegressionStrategy::Mean, 22.),
+        (RegressionStrategy::Median, 3.),
+        (RegressionStrategy::Quantile(0.), 1.),
+        (RegressionStrategy::Quantile(0.875), 52.),
+        (RegressionStrategy::Constant(-1.), -1.),
+    ] {
+        let regressor = DummyRegressorEstimator::new(strategy)
+            .fit(&(&x, &y))
+            .unwrap();
+
+        assert_eq!(regressor.constant(), constant);
+        assert_eq!(
+            regressor.predict(&x).unwrap(),
+            Array1::from_elem(5, constant)
+        );
+    }
+
+    assert!(
+        DummyRegressorEstimator::new(RegressionStrategy::Quantile(1.5))
+            .fit(&(&x, &y))
+            .is_none()
+    );
+    assert!(DummyRegressorEstimator::default()
+        .fit(&(&Array2::zeros((0, 1)), &Array1::zeros(0)))
+        .is_none());
+}
+
+#[test]
+fn adaboost() {
+    let mut rng = StdRng::seed_from_u64(37);
+
+    // diagonal boundaries take many axis aligned stumps to approximate
+    let records: Vec<(Array1<f64>, &str)> = (0..300)
+        .map(|_| {
+            let x: f64 = rng.random_range(-3.0..3.0);
+            let y: f64 = rng.random_range(-3.0..3.0);
+            let label = match (x + y > 1., x - y > 1.) {
+                (true, _) => "upper",
+                (false, true) => "lower",
+                (false, false) => "left",
+            };
+
+            (arr1(&[x, y]), label)
+        })
+        .collect();
+
+    let dataset = ClassificationDataSet::from(records.clone());
+    let truth: Vec<&str> = records.iter().map(|(_, label)| *label).collect();
+    let features = || records.iter().map(|(features, _)| features.clone());
+
+    let stump = DecisionTreeClassifierEstimator::default().with_max_depth(Some(1));
+    let stump_accuracy = accuracy(
+        truth.clone(),
+        stump.fit(&dataset).unwrap().predict(features()).unwrap(),
+    )
+    .unwrap();
+
+    let model = AdaBoostClassifierEstimator::new(stump)
+        .with_n_estimators(200)
+        .fit(&dataset)
+        .unwrap();
+
+    let boosted_accuracy = accuracy(truth.clone(), model.predict(features()).unwrap()).unwrap();
+
+    assert!(stump_accuracy < 0.7);
+    assert!(boosted_accuracy > 0.85);
+    assert_eq!(model.estimator_weights().len(), model.n_estimators());
+    assert!(model
+        .estimator_errors()
+        .iter()
+        .all(|error| *error < 2. / 3.));
+    assert!(model
+        .predict_proba(features())
+        .unwrap()
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+
+    // any estimator accepting sample weights can be boosted
+    let model = AdaBoostClassifierEstimator::new(GaussianNBEstimator::default())
+        .with_n_estimators(10)
+        .with_learning_rate(0.5)
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(model.labels(), model.classifiers()[0].labels());
+    assert!(model.n_estimators() <= 10);
+
+    assert!(AdaBoostClassifierEstimator::new(stump)
+        .with_n_estimators(0)
+        .fit(&dataset)
+        .is_none());
+    assert!(AdaBoostClassifierEstimator::new(stump)
+        .with_learning_rate(0.)
+        .fit(&dataset)
+        .is_none());
+
+    // records without weight do not affect a weighted tree
+    let weights: Vec<f64> = (0..300).map(|idx| (idx % 2) as f64).collect();
+    let odd = ClassificationDataSet::from(records.iter().skip(1).step_by(2).cloned());
+    let tree = DecisionTreeClassifierEstimator::default().with_max_depth(Some(4));
+
+    assert_eq!(
+        tree.fit_weighted(&dataset, &weights)
+            .unwrap()
+            .predict(features())
+            .unwrap(),
+        tree.fit(&odd).unwrap().predict(features()).unwrap()
+    );
+    assert!(tree.fit_weighted(&dataset, &[1.; 3]).is_none());
+}
+
+#[test]
+fn nearest_centroid() {
+    let mut rng = StdRng::seed_from_u64(41);
+
+    // two informative features among many noisy ones
+    let mut draw = |n: usize| -> Vec<(Array1<f64>, &str)> {
+        (0..n)
+            .map(|idx| {
+                let (label, center) = match idx % 2 {
+                    0 => ("first", 1.),
+                    _ => ("second", -1.),
+                };
+
+                let features = Array1::from_shape_fn(50, |feature| match feature {
+                    0 | 1 => center + rng.random_range(-1.0..1.0),
+                    _ => rng.random_range(-3.0..3.0),
+                });
+
+                (features, label)
+            })
+            .collect()
+    };
+
+    let train = draw(40);
+    let test = draw(400);
+
+    let dataset = ClassificationDataSet::from(train.clone());
+    let truth: Vec<&str> = test.iter().map(|(_, label)| *label).collect();
+    let features = || test.iter().map(|(features, _)| features.clone());
+
+    let plain = NearestCentroidEstimator::default().fit(&dataset).unwrap();
+    let shrunk = NearestCentroidEstimator::default()
+        .with_shrink_threshold(Some(2.))
+        .fit(&dataset)
+        .unwrap();
+
+    let overall = train
+        .iter()
+        .fold(Array1::<f64>::zeros(50), |agg, (features, _)| {
+            agg + features
+        })
+        / train.len() as f64;
+    let unshrunk_noise = (2..50)
+        .filter(|feature| {
+            shrunk
+                .centroids()
+                .column(*feature)
+                .iter()
+                .any(|value| (value - overall[*feature]).abs() > 1e-12)
+        })
+        .count();
+
+    assert!(shrunk.centroids().column(0)[0] > overall[0]);
+    assert!(unshrunk_noise < 5);
+    assert!(
+        accuracy(truth.clone(), shrunk.predict(features()).unwrap()).unwrap()
+            > accuracy(truth.clone(), plain.predict(features()).unwrap()).unwrap()
+    );
+    assert!(shrunk
+        .predict_proba(features())
+        .unwrap()
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|total| (total - 1.).abs() < 1e-10));
+
+    let dataset = ClassificationDataSet::from(vec![
+        (arr1(&[0., 0.]), "a"),
+        (arr1(&[1., 0.]), "a"),
+        (arr1(&[9., 3.]), "a"),
+        (arr1(&[5., 5.]), "b"),
+    ]);
+
+    let manhattan = NearestCentroidEstimator::default()
+        .with_metric(Metric::Manhattan)
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(manhattan.centroids(), &arr2(&[[1., 0.], [5., 5.]]));
+    assert!(NearestCentroidEstimator::default()
+        .with_shrink_threshold(Some(-1.))
+        .fit(&dataset)
+        .is_none());
+    assert!(NearestCentroidEstimator::default()
+        .with_metric(Metric::Minkowski(0.5))
+        .fit(&dataset)
+        .is_none());
+
+    let single_label = ClassificationDataSet::from(vec![
+        (arr1(&[0., 0.]), "a"),
+        (arr1(&[1., 2.]), "a"),
+        (arr1(&[2., 1.]), "a"),
+    ]);
+
+    assert!(NearestCentroidEstimator::default()
+        .with_shrink_threshold(Some(0.5))
+        .fit(&single_label)
+        .is_none());
+    assert!(NearestCentroidEstimator::default()
+        .fit(&single_label)
+        .is_some());
+}
+
+#[test]
+fn multilabel() {
+    let mut rng = StdRng::seed_from_u64(43);
+
+    // "both" is tagged on the records tagged "right" which are also above the horizontal axis
+    let mut draw = |n: usize| -> Vec<(Array1<f64>, Vec<&str>)> {
+        (0..n)
+            .map(|_| {
+                let x: f64 = rng.random_range(-1.0..1.0);
+                let y: f64 = rng.random_range(-1.0..1.0);
+
+                let labels = match (x > 0., y > 0.) {
+                    (true, true) => vec!["right", "both"],
+                    (true, false) => vec!["right"],
+                    (false, _) => vec![],
+                };
+
+                (arr1(&[x, y]), labels)
+            })
+            .collect()
+    };
+
+    let train = draw(400);
+    let test = draw(400);
+
+    let dataset = MultiLabelDataSet::from(train);
+    let test = MultiLabelDataSet::from(test);
+    let features = || test.get_features().into_iter().cloned();
+
+    let estimator = LogisticRegressionEstimator::default().with_penalty(Penalty::L2(1e-3));
+
+    let relevance = BinaryRelevanceEstimator::new(estimator)
+        .fit(&dataset)
+        .unwrap();
+    let chain = ClassifierChainEstimator::new(estimator)
+        .fit(&dataset)
+        .unwrap();
+
+    let truth = test.indicator_matrix(relevance.labels());
+
+    assert_eq!(relevance.labels(), chain.labels());
+    assert_eq!(relevance.classifiers().len(), 2);
+    assert_eq!(chain.order(), &[0, 1]);
+
+    let relevance_predictions = relevance.predict(features()).unwrap();
+    let chain_predictions = chain.predict(features()).unwrap();
+
+    assert_eq!(chain_predictions.dim(), (400, 2));
+    assert!(hamming_loss(&truth, &chain_predictions).unwrap() < 0.05);
+    assert!(
+        subset_accuracy(&truth, &chain_predictions).unwrap()
+            > subset_accuracy(&truth, &relevance_predictions).unwrap()
+    );
+    assert_eq!(
+        relevance
+            .predict_proba(features())
+            .unwrap()
+            .mapv(|p| p > 0.5),
+        relevance_predictions
+    );
+
+    assert!(ClassifierChainEstimator::new(estimator)
+        .with_order(Some(vec![0, 0]))
+        .fit(&dataset)
+        .is_none());
+
+    let reversed = ClassifierChainEstimator::new(estimator)
+        .with_order(Some(vec![1, 0]))
+        .fit(&dataset)
+        .unwrap();
+
+    assert_eq!(reversed.order(), &[1, 0]);
+    assert_eq!(reversed.classifiers()[1].coefficients().ncols(), 3);
+
+    let truth = arr2(&[[true, false], [false, false]]);
+    let inference = arr2(&[[true, true], [false, false]]);
+
+    assert_eq!(hamming_loss(&truth, &inference), Some(0.25));
+    assert_eq!(subset_accuracy(&truth, &inference), Some(0.5));
+    assert_eq!(hamming_loss(&truth, &arr2(&[[true, false]])), None);
+}
+
+#[test]
+fn sampling() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let dataset: ClassificationDataSet<Array1<f64>, bool> =
+        ClassificationDataSet::from((0..500).map(|idx| {
+            let positive = idx % 50 == 0;
+            let offset = match positive {
+                true => 5.,
+                false => 0.,
+            };
+
+            (
+                arr1(&[
+                    offset + rng.random_range(0.0..1.0),
+                    offset + rng.random_range(0.0..1.0),
+                ]),
+                positive,
+            )
+        }));
+
+    let count = |dataset: &ClassificationDataSet<Array1<f64>, bool>, label: bool| {
+        dataset
+            .get_labels()
+            .into_iter()
+            .filter(|l| **l == label)
+            .count()
+    };
+
+    let over = RandomOverSampler::default()
+        .with_seed(Some(0))
+        .resample(&dataset)
+        .unwrap();
+
+    assert_eq!(count(&over, true), 490);
+    assert_eq!(count(&over, false), 490);
+    assert_eq!(over.get_features()[..500], dataset.get_features()[..]);
+
+    let under = RandomUnderSampler::new(SamplingStrategy::Ratio(0.5))
+        .with_seed(Some(0))
+        .resample(&dataset)
+        .unwrap();
+
+    assert_eq!(count(&under, true), 10);
+    assert_eq!(count(&under, false), 20);
+    assert!(under
+        .get_records()
+        .iter()
+        .all(|record| dataset.get_features().contains(&&record.features)));
+
+    let smote = SMOTE::new(SamplingStrategy::PerLabel(vec![(true, 0.2)])).with_seed(Some(0));
+    let synthetic = smote.resample(&dataset).unwrap();
+
+    assert_eq!(count(&synthetic, true), 98);
+    assert_eq!(count(&synthetic, false), 490);
+    assert!(synthetic.get_records()[500..]
+        .iter()
+        .all(|record| record.label && record.features.iter().all(|v| (5.0..6.0).contains(v))));
+    assert_eq!(
+        synthetic.get_features(),
+        smote.resample(&dataset).unwrap().get_features()
+    );
+
+    assert!(RandomOverSampler::new(SamplingStrategy::Ratio(1.5))
+        .resample(&dataset)
+        .is_none());
+    assert!(
+        RandomUnderSampler::new(SamplingStrategy::PerLabel(vec![(true, 0.)]))
+            .resample(&dataset)
+            .is_none()
+    );
+    assert!(SMOTE::default()
+        .with_k_neighbors(0)
+        .resample(&dataset)
+        .is_none());
+
+    let single_positive: ClassificationDataSet<Array1<f64>, bool> =
+        ClassificationDataSet::from(vec![
+            (arr1(&[0.]), false),
+            (arr1(&[1.]), false),
+            (arr1(&[2.]), false),
+            (arr1(&[5.]), true),
+        ]);
+
+    assert!(SMOTE::default()
+        .with_seed(Some(0))
+        .resample(&single_positive)
+        .is_none());
+    assert_eq!(
+        count(
+            &RandomOverSampler::default()
+                .with_seed(Some(0))
+                .resample(&single_positive)
+                .unwrap(),
+            true
+        ),
+        3
+    );
+}